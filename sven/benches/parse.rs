@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use sven::{analyze, Config, WeakCommit};
+
+const HEADER: &str = "feat(parser)!: rebuild the row splitting without the per-row probing";
+
+const MESSAGE: &str = "\
+feat(parser)!: rebuild the row splitting without the per-row probing
+
+Rows and tokens are scanned in a single pass over the message, producing
+the same rows and blocks as before.
+
+Кириллица, 漢字 and emoji 🚀 take several bytes each.
+
+BREAKING CHANGE: the probe rule is gone from the grammar
+Refs: #37
+";
+
+fn messages() -> Vec<(&'static str, String)> {
+    vec![
+        ("header", HEADER.to_string()),
+        ("message", MESSAGE.to_string()),
+        (
+            "long body",
+            format!("{}\n{}", HEADER, "body line\n".repeat(1000)),
+        ),
+    ]
+}
+
+fn weak_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("WeakCommit::parse");
+    for (name, message) in messages() {
+        group.throughput(Throughput::Bytes(message.len() as u64));
+        group.bench_function(name, |b| b.iter(|| WeakCommit::parse(black_box(&message))));
+        // the pest implementation the scanner replaced, see the `baseline` feature
+        #[cfg(feature = "baseline")]
+        group.bench_function(format!("{} (pest)", name), |b| {
+            b.iter(|| sven::baseline::parse(black_box(&message)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn analyze_commit(c: &mut Criterion) {
+    let config = Config::default();
+    let mut group = c.benchmark_group("analyze");
+    for (name, message) in messages() {
+        group.throughput(Throughput::Bytes(message.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| analyze(black_box(&message), &config).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, weak_commit, analyze_commit);
+criterion_main!(benches);
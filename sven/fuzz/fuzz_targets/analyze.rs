@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sven::{analyze_bytes, Cleanup, Config};
+
+// no input is expected to crash the analysis, errors are fine
+fuzz_target!(|data: &[u8]| {
+    for cleanup in [Cleanup::Strip, Cleanup::Verbatim] {
+        let mut config = Config::default();
+        config.cleanup = cleanup;
+        let _ = analyze_bytes(data, &config);
+    }
+});
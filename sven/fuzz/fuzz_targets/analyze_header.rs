@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sven::{fuzzing::analyze_header, Config, Error, WeakCommit};
+
+// every block of the solution points within the input, on char boundaries
+fuzz_target!(|commit: &str| {
+    let w = WeakCommit::parse(commit);
+
+    let solution = match analyze_header(commit, &Config::default(), w.header) {
+        Ok(solution) => solution,
+        Err(Error::BudgetExceeded(_)) => return,
+        Err(e) => panic!("{}", e),
+    };
+
+    for bytes in solution.iter().filter_map(|block| block.bytes) {
+        assert!(bytes.end() <= commit.len());
+        assert!(commit.is_char_boundary(bytes.start()));
+        assert!(commit.is_char_boundary(bytes.end()));
+    }
+});
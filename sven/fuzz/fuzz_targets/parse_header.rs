@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sven::fuzzing::parse_header;
+
+// blocks after the root one cover the whole header one after another,
+// on char boundaries
+fuzz_target!(|header: &str| {
+    let blocks = parse_header(header);
+
+    let mut end = 0;
+    for bytes in blocks.iter().filter_map(|block| block.bytes) {
+        assert_eq!(bytes.start(), end);
+        assert!(header.is_char_boundary(bytes.end()));
+        end = bytes.end();
+    }
+    assert_eq!(end, header.len());
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sven::WeakCommit;
+
+// rows cover the whole input one after another, on char boundaries
+fuzz_target!(|commit: &str| {
+    let w = WeakCommit::parse(commit);
+
+    let mut end = 0;
+    for row in &w.rows {
+        assert_eq!(row.bytes.start(), end);
+        assert!(commit.is_char_boundary(row.bytes.end()));
+        end = row.bytes.end();
+    }
+    assert_eq!(end, commit.len());
+});
@@ -1,23 +1,569 @@
 use crate::{
     analyze_header::analyze_header,
-    config::{Config, TypeRule},
+    block::{Block, Status, Val},
+    bytes::Bytes,
+    cleanup::{self, Cleaned},
+    config::{Config, ScopeRule, TypeRule},
+    conventional_commit::{git_reverted, Trailers, SEPARATORS},
+    domain::{Domain, Scope},
+    error::Error,
+    footer_issue::footer,
+    header_issue::header,
+    ignore::{is_ignored, wrapper_len},
     report::Report,
-    weak_commit::WeakCommit,
+    shape_issue::shape,
+    weak_commit::{header_at, WeakCommit},
 };
-use anyhow::Result;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::OnceLock};
 
-pub fn analyze(commit: &str) -> Result<Report> {
-    let weak_commit = WeakCommit::parse(commit)?;
+/// Analyse the commit message against the conventional commits
+/// specification, reporting every issue found
+///
+/// ```
+/// use sven::{analyze, Config};
+///
+/// let report = analyze("fix: me", &Config::default()).unwrap();
+/// assert!(report.is_ok());
+///
+/// let report = analyze("fix me", &Config::default()).unwrap();
+/// assert_eq!(report.header.len(), 1);
+/// ```
+pub fn analyze(commit: &str, config: &Config) -> Result<Report, Error> {
+    let cleaned = Cleaned::new(commit, config.cleanup, config.comment_char);
+    if cleaned.text.trim().is_empty() {
+        return Err(Error::Empty);
+    }
+    let header = cleaned.text.lines().find(|line| !line.trim().is_empty());
+    if header.is_some_and(|header| is_ignored(header, config)) {
+        return Ok(Report::default());
+    }
+    let report = analyze_cleaned(&cleaned.text, config)?;
 
-    let config = Config {
-        type_rule: TypeRule::AnyFirstSeq,
-    };
-    analyze_header(commit, &config, weak_commit.header);
+    let mut report = restore(report, &cleaned, commit);
+    report.shape.extend(analyze_line_endings(commit));
+    Ok(report)
+}
+
+/// Same as [`analyze`] for the commit which is yet to be checked
+/// to be valid UTF-8
+///
+/// ```
+/// use sven::{analyze_bytes, Config, Error};
+///
+/// let report = analyze_bytes(b"fix: \xff", &Config::default());
+/// assert_eq!(report.unwrap_err(), Error::InvalidUtf8(5));
+/// ```
+pub fn analyze_bytes(commit: &[u8], config: &Config) -> Result<Report, Error> {
+    analyze(std::str::from_utf8(commit)?, config)
+}
+
+fn analyze_cleaned(commit: &str, config: &Config) -> Result<Report, Error> {
+    let weak_commit = WeakCommit::parse(commit);
+    let (solution, header) = analyze_first_row(commit, config, &weak_commit)?;
+
+    let mut footers = HashMap::new();
+    let missing = analyze_required_footers(commit, config);
+    if !missing.is_empty() {
+        // missing footers are expected right after the last row
+        let row = weak_commit.rows.last().map_or(1, |row| row.row + 1);
+        footers.insert(row, missing);
+    }
 
     Ok(Report {
-        header: Vec::new(),
-        shape: (),
-        footers: HashMap::new(),
+        header,
+        shape: Vec::new(),
+        footers,
+        solution,
+        changed_scopes: Vec::new(),
     })
 }
+
+/// Analyse the header, the first row of the commit, returning
+/// the solution picked along with the issues found in it
+pub(crate) fn analyze_first_row(
+    commit: &str,
+    config: &Config,
+    weak_commit: &WeakCommit,
+) -> Result<(Vec<Block>, Vec<header::Issue>), Error> {
+    if let Some(reverted) = git_revert(commit, weak_commit) {
+        return Ok((vec![Block::root(), reverted], Vec::new()));
+    }
+
+    // the header wrapped by `fixup!` and `squash!` is analysed on its own
+    let blocks = match weak_commit.rows.first() {
+        Some(row) => match row.bytes.capture(commit).map_or(0, wrapper_len) {
+            0 => weak_commit.header.clone(),
+            skip => header_at(commit, row, skip),
+        },
+        None => weak_commit.header.clone(),
+    };
+
+    let solution = analyze_header(commit, config, blocks.clone())?;
+    let (mut solution, unknown) = settle_type(commit, config, blocks, solution)?;
+    let trailing = trim_desc(commit, &mut solution);
+
+    let mut header: Vec<header::Issue> = unknown.into_iter().collect();
+    header.extend(header::Issue::collect(&solution));
+    header.extend(analyze_scope(commit, config, &solution));
+    header.extend(analyze_punctuation(commit, &solution));
+    header.extend(trailing);
+    Ok((solution, header))
+}
+
+/// Map everything in the report of the cleaned up commit
+/// back to the original commit
+fn restore(report: Report, cleaned: &Cleaned, commit: &str) -> Report {
+    let bytes = |bytes| cleaned.original_bytes(bytes);
+
+    // the row is found by its first byte, rows past the end of
+    // the cleaned up commit are counted from its last row
+    let rows: Vec<usize> = cleaned
+        .text
+        .split_inclusive('\n')
+        .scan((0, 0, 1), |(start, counted, row), text| {
+            // rows of the original are counted as far as the next
+            // row goes, every row of the original is counted once
+            let original = cleaned.original(*start);
+            *row += commit
+                .get(*counted..original)
+                .map_or(0, |rows| rows.matches('\n').count());
+            *counted = original;
+            *start += text.len();
+            Some(*row)
+        })
+        .collect();
+    let row = |row: usize| match rows.get(row.saturating_sub(1)) {
+        Some(&original) => original,
+        None => rows.last().copied().unwrap_or_default() + row - rows.len(),
+    };
+
+    Report {
+        header: report.header.iter().map(|issue| issue.map(bytes)).collect(),
+        shape: report.shape,
+        footers: report
+            .footers
+            .into_iter()
+            .map(|(n, issues)| (row(n), issues))
+            .collect(),
+        solution: report
+            .solution
+            .into_iter()
+            .map(|block| Block {
+                bytes: block.bytes.map(bytes),
+                ..block
+            })
+            .collect(),
+        changed_scopes: report.changed_scopes,
+    }
+}
+
+/// The header git writes when reverting is taken as is, the header of the
+/// reverted commit in quotes is settled as the description
+fn git_revert(commit: &str, weak_commit: &WeakCommit) -> Option<Block> {
+    let row = weak_commit.rows.first()?;
+    let header = row.bytes.capture(commit)?.trim_end_matches(['\n', '\r']);
+    let reverted = git_reverted(header)?;
+
+    let start = row.bytes.start() + "Revert \"".len();
+    Some(Block {
+        val: Val::Seq,
+        domain: Domain::Desc,
+        bytes: Some(Bytes::new(start, start + reverted.len())),
+        status: Status::Settled,
+    })
+}
+
+fn analyze_scope(commit: &str, config: &Config, solution: &[Block]) -> Option<header::Issue> {
+    let ScopeRule::Strict(set) = &config.scope_rule else {
+        return None;
+    };
+
+    let scope = solution.iter().find(|block| {
+        block.domain == Domain::Scope(Scope::Scope) && block.status == Status::Settled
+    })?;
+
+    match scope.capture(commit) {
+        Some(val) if set.contains(val) => None,
+        _ => Some(header::Issue::Unknown(scope.domain, scope.bytes?)),
+    }
+}
+
+/// Solution with the first word settled as the type when none of the types
+/// of the config is found, the way the scope is settled whatever it is, so
+/// that `chore: me` is reported as the unknown type rather than as the
+/// missing type, colon and space
+fn settle_type(
+    commit: &str,
+    config: &Config,
+    blocks: Vec<Block>,
+    solution: Vec<Block>,
+) -> Result<(Vec<Block>, Option<header::Issue>), Error> {
+    static ANY: OnceLock<Config> = OnceLock::new();
+
+    let TypeRule::Strict(set) = &config.type_rule else {
+        return Ok((solution, None));
+    };
+    let missing = solution
+        .iter()
+        .any(|block| block.domain == Domain::Type && block.status == Status::Missing);
+    if !missing {
+        return Ok((solution, None));
+    }
+
+    let any = ANY.get_or_init(|| Config {
+        type_rule: TypeRule::AnyFirstSeq,
+        ignore: Vec::new(),
+        ..Config::default()
+    });
+    let settled = analyze_header(commit, any, blocks)?;
+    let kind = settled
+        .iter()
+        .find(|block| block.domain == Domain::Type && block.status == Status::Settled)
+        .and_then(|block| Some((block.bytes?, block.capture(commit)?)));
+
+    match kind {
+        Some((bytes, kind)) if !set.contains(config.canonical(kind)) => {
+            Ok((settled, Some(header::Issue::Unknown(Domain::Type, bytes))))
+        }
+        _ => Ok((solution, None)),
+    }
+}
+
+/// Look-alikes of the punctuation and runs of whitespace, settled in place
+/// of the punctuation
+fn analyze_punctuation<'s>(
+    commit: &'s str,
+    solution: &'s [Block],
+) -> impl Iterator<Item = header::Issue> + 's {
+    solution
+        .iter()
+        .filter(|block| block.status == Status::Settled)
+        .filter_map(move |block| {
+            let expected = block.domain.punctuation()?;
+            let bytes = block.bytes?;
+            match block.capture(commit) {
+                Some(val) if val == expected => None,
+                Some(val) if val.chars().count() == 1 && !val.is_ascii() => {
+                    Some(header::Issue::WrongChar(block.domain, bytes))
+                }
+                _ => Some(header::Issue::SpaceRun(block.domain, bytes)),
+            }
+        })
+}
+
+/// Cut the trailing whitespace off the description, the same
+/// whitespace the header ends with
+fn trim_desc(commit: &str, solution: &mut [Block]) -> Option<header::Issue> {
+    let desc = solution
+        .iter_mut()
+        .find(|block| block.domain == Domain::Desc)?;
+    let bytes = desc.bytes?;
+    let trimmed = desc.capture(commit)?.trim_end();
+    if trimmed.is_empty() || trimmed.len() == bytes.total() {
+        return None;
+    }
+
+    let end = bytes.start() + trimmed.len();
+    desc.bytes = Some(Bytes::new(bytes.start(), end));
+    Some(header::Issue::Trailing(
+        Domain::Space,
+        Bytes::new(end, bytes.end()),
+    ))
+}
+
+/// The first row ending differently from the rows before it, the original
+/// commit is checked as the line endings are gone after the cleanup
+fn analyze_line_endings(commit: &str) -> Option<shape::Issue> {
+    let mut endings = cleanup::lines(commit)
+        .enumerate()
+        .filter(|(_, (_, _, eol))| !eol.is_empty());
+    let (_, (_, _, first)) = endings.next()?;
+
+    endings
+        .find(|(_, (_, _, eol))| eol != &first)
+        .map(|(i, _)| shape::Issue::MixedLineEndings(i + 1))
+}
+
+fn analyze_required_footers(commit: &str, config: &Config) -> Vec<footer::Issue> {
+    if config.required_footers.is_empty() {
+        return Vec::new();
+    }
+
+    let footers = Trailers::parse(commit, SEPARATORS, config.comment_char)
+        .map(|trailers| trailers.footers)
+        .unwrap_or_default();
+
+    config
+        .required_footers
+        .iter()
+        .filter(|token| {
+            !footers
+                .iter()
+                .any(|footer| footer.token().eq_ignore_ascii_case(token))
+        })
+        .map(|token| footer::Issue::Missing(token.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleanup::Cleanup;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
+
+    #[test]
+    fn comments_are_ignored() {
+        let commit = "# comment\nfix: me\n\n# Please enter the commit message\n";
+        let report = analyze(commit, &Config::default()).unwrap();
+        assert_eq!(report.header, vec![]);
+    }
+
+    #[test]
+    fn bytes_of_the_original() {
+        let commit = "\n# comment\nfix me\n";
+        let report = analyze(commit, &Config::default()).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Missing(Domain::Colon, 14)]
+        );
+        assert_eq!(report.solution[1].capture(commit), Some("fix"));
+    }
+
+    #[test]
+    fn verbatim() {
+        let config = Config {
+            cleanup: Cleanup::Verbatim,
+            ..Config::default()
+        };
+        let report = analyze("# fix: me", &config).unwrap();
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn footers_row_of_the_original() {
+        let config = Config {
+            required_footers: vec!["Refs".to_string()],
+            ..Config::default()
+        };
+        let report = analyze("fix: me\n\n\n\nbody\n# comment\n", &config).unwrap();
+        assert_eq!(
+            report.footers,
+            HashMap::from([(6, vec![footer::Issue::Missing("Refs".to_string())])])
+        );
+    }
+
+    #[test]
+    fn unknown_scope() {
+        let config = Config {
+            scope_rule: ScopeRule::Strict(HashSet::from_iter(["app".to_string()])),
+            ..Config::default()
+        };
+        assert_eq!(analyze("fix(app): me", &config).unwrap().header, vec![]);
+        assert_eq!(
+            analyze("fix(ui): me", &config).unwrap().header,
+            vec![header::Issue::Unknown(
+                Domain::Scope(Scope::Scope),
+                Bytes::new(4, 6)
+            )]
+        );
+    }
+
+    #[test]
+    fn wrong_punctuation() {
+        let report = analyze("fix（app）：\u{a0}да", &Config::default()).unwrap();
+        assert_eq!(
+            report.header,
+            vec![
+                header::Issue::WrongChar(Domain::Scope(Scope::OpenBracket), Bytes::new(3, 6)),
+                header::Issue::WrongChar(Domain::Scope(Scope::CloseBracket), Bytes::new(9, 12)),
+                header::Issue::WrongChar(Domain::Colon, Bytes::new(12, 15)),
+                header::Issue::WrongChar(Domain::Space, Bytes::new(15, 17)),
+            ]
+        );
+        assert_eq!(
+            report.solution[3].capture("fix（app）：\u{a0}да"),
+            Some("app")
+        );
+    }
+
+    #[test]
+    fn whitespace() {
+        let config = Config {
+            cleanup: Cleanup::Verbatim,
+            ..Config::default()
+        };
+        let report = analyze(" \tfix:  me \t\n", &config).unwrap();
+        assert_eq!(
+            report.header,
+            vec![
+                header::Issue::Leading(Domain::Space, Bytes::new(0, 2)),
+                header::Issue::SpaceRun(Domain::Space, Bytes::new(6, 8)),
+                header::Issue::Trailing(Domain::Space, Bytes::new(10, 12)),
+            ]
+        );
+        assert_eq!(report.solution[5].bytes, Some(Bytes::new(8, 10)));
+    }
+
+    #[test]
+    fn line_endings() {
+        let report = analyze("fix: me\r\n\r\nbody\r\n", &Config::default()).unwrap();
+        assert!(report.is_ok());
+
+        let report = analyze("fix: me\r\n\nbody\r\n", &Config::default()).unwrap();
+        assert_eq!(report.shape, vec![shape::Issue::MixedLineEndings(2)]);
+    }
+
+    #[test]
+    fn errors() {
+        let config = Config::default();
+        assert_eq!(analyze("", &config).unwrap_err(), Error::Empty);
+        assert_eq!(
+            analyze(" \n# comment\n", &config).unwrap_err(),
+            Error::Empty
+        );
+        assert_eq!(
+            analyze_bytes(b"fix: \xd0", &config).unwrap_err(),
+            Error::InvalidUtf8(5)
+        );
+    }
+
+    #[test]
+    fn odd_inputs() {
+        let config = Config::default();
+        for commit in [
+            ":",
+            "(((",
+            "!\r\n\r\n:",
+            "\r\rfix",
+            "\u{a0}\u{3000}x",
+            "：\t（",
+        ] {
+            assert!(analyze(commit, &config).is_ok(), "{:?}", commit);
+        }
+    }
+
+    #[test]
+    fn required_footers() {
+        let config = Config {
+            required_footers: vec!["Refs".to_string()],
+            ..Config::default()
+        };
+        assert!(analyze("fix: me\n\nrefs: #1", &config).unwrap().is_ok());
+
+        let report = analyze("fix: me\n\nbody", &config).unwrap();
+        assert_eq!(
+            report.footers,
+            HashMap::from([(4, vec![footer::Issue::Missing("Refs".to_string())])])
+        );
+    }
+
+    #[test]
+    fn required_footers_comment_char() {
+        let config = Config::parse(
+            r#"
+footers = ["Refs"]
+cleanup = "whitespace"
+comment_char = ";"
+"#,
+        )
+        .unwrap();
+        // the comment is left in the message, and skipped within the trailers
+        let commit = "fix: me\n\nRefs: #1\n; Reviewed-by: nobody yet\n";
+        assert!(analyze(commit, &config).unwrap().is_ok());
+
+        let report = analyze("fix: me\n\nbody\n\n;Refs: #1\n", &config).unwrap();
+        assert_eq!(
+            report.footers,
+            HashMap::from([(6, vec![footer::Issue::Missing("Refs".to_string())])])
+        );
+    }
+
+    #[test]
+    fn unknown_type() {
+        let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+        let report = analyze("chore(app): me", &config).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Unknown(Domain::Type, Bytes::new(0, 5))]
+        );
+
+        let report = analyze("chore me", &config).unwrap();
+        assert_eq!(
+            report.header,
+            vec![
+                header::Issue::Unknown(Domain::Type, Bytes::new(0, 5)),
+                header::Issue::Missing(Domain::Colon, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn git_revert() {
+        let commit = "# comment\nRevert \"feat(app): me\"\n\nThis reverts commit 1a2b3c4d.\n";
+        let config = Config {
+            type_rule: TypeRule::Strict(HashSet::from(["fix".to_string()])),
+            ..Config::default()
+        };
+        let report = analyze(commit, &config).unwrap();
+        assert_eq!(report.header, vec![]);
+
+        let header = report.commit_header(commit).unwrap();
+        assert_eq!((header.kind, header.desc), ("revert", "feat(app): me"));
+    }
+
+    #[test]
+    fn ignored() {
+        let config = Config::parse(r#"ignore = ["^WIP "]"#).unwrap();
+        for commit in ["Merge branch 'me'\n", "# comment\nWIP me\n"] {
+            let report = analyze(commit, &config).unwrap();
+            assert!(report.is_ok(), "{}", commit);
+            assert_eq!(report.commit_header(commit), None);
+        }
+    }
+
+    #[test]
+    fn wrapped() {
+        let commit = "fixup! fix me\n";
+        let report = analyze(commit, &Config::default()).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Missing(Domain::Colon, 10)]
+        );
+
+        let commit = "squash! fix(app): me\n";
+        let report = analyze(commit, &Config::default()).unwrap();
+        let header = report.commit_header(commit).unwrap();
+        assert_eq!((header.kind, header.scope), ("fix", Some("app")));
+    }
+
+    #[test]
+    fn aliases() {
+        let config = Config::parse(
+            r#"
+types = ["feat", "fix"]
+
+[aliases]
+"исправление" = "fix"
+"✨" = "feat"
+bugfix = "fix"
+docs = "documentation"
+"#,
+        )
+        .unwrap();
+        for (commit, kind) in [
+            ("исправление: да", "fix"),
+            ("✨(ui)!: me", "feat"),
+            ("bugfix: me", "fix"),
+        ] {
+            let report = analyze(commit, &config).unwrap();
+            assert_eq!(report.header, vec![], "{}", commit);
+            let header = report.commit_header(commit).unwrap().canonical(&config);
+            assert_eq!(header.kind, kind);
+        }
+
+        // the alias of the type which is not allowed is not allowed either
+        let report = analyze("docs: me", &config).unwrap();
+        assert!(!report.is_ok());
+    }
+}
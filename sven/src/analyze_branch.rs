@@ -0,0 +1,185 @@
+use crate::{
+    branch_issue::branch,
+    bytes::Bytes,
+    config::{Config, ScopeRule, TypeRule},
+    glob,
+};
+
+/// Analyse the name of the branch: it is expected to start with one of the
+/// types followed by `/`, then the description. When the scopes are
+/// configured, the description starts with one of them, or the issue number,
+/// followed by `-`. Exempt branches, such as `main`, are not analysed.
+/// The aliases of the types are accepted as the types they stand for.
+///
+/// The bytes of the issues point to the name as given, `refs/heads/` included.
+///
+/// ```
+/// use sven::{analyze_branch, branch, Bytes, Config};
+///
+/// let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+/// assert_eq!(analyze_branch("feat/ui-login", &config), vec![]);
+/// assert_eq!(
+///     analyze_branch("feature/ui-login", &config),
+///     vec![branch::Issue::UnknownType(Bytes::new(0, 7))]
+/// );
+/// ```
+pub fn analyze_branch(name: &str, config: &Config) -> Vec<branch::Issue> {
+    let full = name.len();
+    let name = name.strip_prefix("refs/heads/").unwrap_or(name);
+    let prefix = full - name.len();
+    if config
+        .exempt_branches
+        .iter()
+        .any(|glob| glob::matches(glob, name))
+    {
+        return Vec::new();
+    }
+
+    let Some((kind, rest)) = name.split_once('/').filter(|(kind, _)| !kind.is_empty()) else {
+        return vec![branch::Issue::MissingType];
+    };
+
+    let mut issues = Vec::new();
+    let types = match &config.type_rule {
+        TypeRule::AnyFirstSeq => None,
+        TypeRule::Strict(set) | TypeRule::Like(set) => Some(set),
+    };
+    if types.is_some_and(|set| !set.contains(config.canonical(kind))) {
+        let bytes = Bytes::new(prefix, prefix + kind.len());
+        issues.push(branch::Issue::UnknownType(bytes));
+    }
+
+    let mut start = prefix + kind.len() + 1;
+    let mut desc = rest;
+    if let ScopeRule::Strict(scopes) = &config.scope_rule {
+        let (scope, after) = rest.split_once('-').unwrap_or((rest, ""));
+        let is_issue = !scope.is_empty() && scope.chars().all(|c| c.is_ascii_digit());
+        if !is_issue && !scopes.contains(scope) {
+            let bytes = Bytes::new(start, start + scope.len());
+            issues.push(branch::Issue::UnknownScope(bytes));
+        }
+        start += rest.len() - after.len();
+        desc = after;
+    }
+
+    if desc.is_empty() {
+        issues.push(branch::Issue::MissingDesc(start));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod names {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn config() -> Config {
+        Config::parse(
+            r#"
+types = ["feat", "fix"]
+scopes = ["ui"]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn valid() {
+        for name in [
+            "feat/ui-login",
+            "fix/123-crash",
+            "refs/heads/fix/ui-a-b",
+            "main",
+        ] {
+            assert_eq!(analyze_branch(name, &config()), vec![], "{}", name);
+        }
+    }
+
+    #[test]
+    fn any_type_and_scope() {
+        let config = Config::default();
+        assert_eq!(analyze_branch("chore/bump-deps", &config), vec![]);
+        assert_eq!(
+            analyze_branch("login", &config),
+            vec![branch::Issue::MissingType]
+        );
+        assert_eq!(
+            analyze_branch("/login", &config),
+            vec![branch::Issue::MissingType]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            (
+                "feature/ui-login",
+                vec![branch::Issue::UnknownType(Bytes::new(0, 7))],
+            ),
+            (
+                "fix/api-crash",
+                vec![branch::Issue::UnknownScope(Bytes::new(4, 7))],
+            ),
+            (
+                "fix/crash",
+                vec![
+                    branch::Issue::UnknownScope(Bytes::new(4, 9)),
+                    branch::Issue::MissingDesc(9),
+                ],
+            ),
+            ("fix/ui-", vec![branch::Issue::MissingDesc(7)]),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(analyze_branch(name, &config()), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn full_ref() {
+        let cases = [
+            (
+                "refs/heads/feature/ui-login",
+                vec![branch::Issue::UnknownType(Bytes::new(11, 18))],
+            ),
+            (
+                "refs/heads/fix/crash",
+                vec![
+                    branch::Issue::UnknownScope(Bytes::new(15, 20)),
+                    branch::Issue::MissingDesc(20),
+                ],
+            ),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(analyze_branch(name, &config()), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn aliases() {
+        let config = Config::parse(
+            r#"
+types = ["feat", "fix"]
+
+[aliases]
+bugfix = "fix"
+"#,
+        )
+        .unwrap();
+        assert_eq!(analyze_branch("bugfix/crash", &config), vec![]);
+        assert_eq!(
+            analyze_branch("hotfix/crash", &config),
+            vec![branch::Issue::UnknownType(Bytes::new(0, 6))]
+        );
+    }
+
+    #[test]
+    fn exempt() {
+        let config = Config::parse(r#"branches = ["release/*"]"#).unwrap();
+        assert_eq!(analyze_branch("release/1.x", &config), vec![]);
+        assert_eq!(
+            analyze_branch("main", &config),
+            vec![branch::Issue::MissingType]
+        );
+    }
+}
@@ -1,31 +1,67 @@
 use crate::{
     block::{Block, Status, Val},
     config::{Config, TypeRule},
-    domain::Domain,
+    domain::{Domain, Scope},
+    error::Error,
 };
+use std::collections::HashMap;
 
-#[derive(Debug, Default)]
-pub struct Portal {
-    pub found_at: usize,
-    pub pointing_at: Option<usize>,
+/// How many steps the analysis of a single header may take, far more than
+/// any header written by a human needs
+const BUDGET: usize = 1 << 20;
+
+/// How much it costs to insert a block which is not in the input
+const MISSING: usize = 2;
+
+/// How much it costs to ignore a block from the input
+const EXTRA: usize = 1;
+
+/// How much it costs to move a block from one place to another
+const MISPLACED: usize = 1;
+
+/// A block waiting to be connected with its counterpart
+/// later in the input
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Portal {
+    /// The portal is at the given index of the solution, waiting for the
+    /// misplaced block to be found later in the input
+    Open(usize),
+
+    /// The misplaced block is at the given index of the solution, waiting
+    /// for the place where it should be
+    Ref(usize),
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
 pub struct Portals {
-    pub kind: Option<Portal>,
     pub colon: Option<Portal>,
     pub space: Option<Portal>,
 }
 
 impl Portals {
     pub fn is_empty(&self) -> bool {
-        self.kind.is_none() && self.colon.is_none() && self.space.is_none()
+        self.colon.is_none() && self.space.is_none()
+    }
+
+    fn get_mut(&mut self, domain: Domain) -> Option<&mut Option<Portal>> {
+        match domain {
+            Domain::Colon => Some(&mut self.colon),
+            Domain::Space => Some(&mut self.space),
+            _ => None,
+        }
     }
-}
 
-impl Portal {
-    pub fn is_connected(&self) -> bool {
-        self.pointing_at.is_some()
+    /// Portals without indexes, so that two branches waiting for the same
+    /// kind of blocks can be compared
+    fn shape(&self) -> (u8, u8) {
+        fn shape(portal: Option<Portal>) -> u8 {
+            match portal {
+                None => 0,
+                Some(Portal::Open(_)) => 1,
+                Some(Portal::Ref(_)) => 2,
+            }
+        }
+        (shape(self.colon), shape(self.space))
     }
 }
 
@@ -37,7 +73,7 @@ pub struct Candidate {
 
 impl PartialOrd for Candidate {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.weight.partial_cmp(&other.weight)
+        Some(self.cmp(other))
     }
 }
 
@@ -47,240 +83,473 @@ impl Ord for Candidate {
     }
 }
 
-/// Analyse header blocks returning an optimal solution
-/// that fulfills the conventional commit specification
-pub fn analyze_header(commit: &str, config: &Config, blocks: Vec<Block>) -> Vec<Block> {
-    let mut all_solutions = Vec::new();
+/// A single path through the header blocks
+#[derive(Debug, Clone)]
+struct Branch {
+    /// Index of the next input block to look at
+    i: usize,
+
+    /// The domain we expect to see next
+    expect: Domain,
 
-    find_solutions(commit, config, blocks, &mut all_solutions);
+    weight: usize,
+    portals: Portals,
 
-    pick_solution(all_solutions)
+    /// Whether at least one block of the description has been taken
+    desc: bool,
+
+    solution: Vec<Block>,
 }
 
-fn pick_solution(all: Vec<Candidate>) -> Vec<Block> {
-    if all.is_empty() {
-        Vec::new()
-    } else if all.len() == 1 {
-        all[0].solution.clone()
-    } else {
-        let mut all = all;
-        all.sort();
-        all[0].solution.clone()
-    }
+type Seen = (usize, Domain, (u8, u8), bool);
+
+struct Solver<'a> {
+    commit: &'a str,
+    config: &'a Config,
+    blocks: &'a [Block],
+    best: Option<Candidate>,
+    seen: HashMap<Seen, usize>,
+    steps: usize,
+    budget: usize,
 }
 
-fn find_solutions(
+/// Analyse header blocks returning an optimal solution
+/// that fulfills the conventional commit specification
+pub fn analyze_header(
     commit: &str,
     config: &Config,
-    candidate: Vec<Block>,
-    solutions: &mut Vec<Candidate>,
-) {
-    let q = vec![Domain::Type, Domain::Colon, Domain::Space, Domain::Desc];
-    let mut q = q.iter().peekable();
-
-    let mut portals = Portals::default();
-    let mut weight: usize = 0;
-
-    let mut candidate = candidate;
-    let blocks_iter = {
-        let mut iter = candidate.iter_mut().enumerate();
-        iter.next(); // skip root
-        iter
-    };
+    blocks: Vec<Block>,
+) -> Result<Vec<Block>, Error> {
+    solve(commit, config, blocks, BUDGET)
+}
 
-    macro_rules! try_missing {
-        ($i:expr, $val:expr) => {
-            let block = Block {
-                val: $val,
-                domain: $val.into(),
-                bytes: None,
-                status: Status::Missing,
-            };
+fn solve(
+    commit: &str,
+    config: &Config,
+    blocks: Vec<Block>,
+    budget: usize,
+) -> Result<Vec<Block>, Error> {
+    if let TypeRule::Like(_) = config.type_rule {
+        return Err(Error::Unsupported("`TypeRule::Like`"));
+    }
 
-            let mut alternative = candidate.clone();
-            alternative.insert($i, block);
+    let root = blocks.first().copied().unwrap_or_else(Block::root);
 
-            find_solutions(commit, config, alternative, solutions);
-        };
-    }
-    macro_rules! try_misplaced {
-        ($i:expr, $val:expr) => {
-            let block = Block {
-                val: $val,
-                domain: $val.into(),
-                bytes: None,
-                status: Status::Misplaced,
-            };
+    let mut solver = Solver {
+        commit,
+        config,
+        blocks: blocks.get(1..).unwrap_or_default(),
+        best: None,
+        seen: HashMap::new(),
+        steps: 0,
+        budget,
+    };
 
-            let mut alternative = candidate.clone();
-            alternative.insert($i, block);
+    solver.find_solutions(Branch {
+        i: 0,
+        expect: Domain::Type,
+        weight: 0,
+        portals: Portals::default(),
+        desc: false,
+        solution: vec![root],
+    });
 
-            find_solutions(commit, config, alternative, solutions);
-        };
+    if solver.steps > budget {
+        return Err(Error::BudgetExceeded(budget));
     }
 
-    for (i, block) in blocks_iter {
-        let q_domain = q.peek();
+    solver
+        .best
+        .map(|c| c.solution)
+        .ok_or(Error::Invariant("no solution found for the header"))
+}
 
-        match block.status {
-            Status::Portal(dest) => {
-                weight += 1;
-                match dest {
-                    Some(_) => {
-                        // if we get here it means we already set destination
-                        // for the current portal? but when?
-                        todo!()
-                    }
-                    None => match block.domain {
-                        Domain::Type => {
-                            portals.kind = Some(Portal {
-                                found_at: i,
-                                pointing_at: None,
-                            });
-                        }
-                        Domain::Colon => {
-                            portals.colon = Some(Portal {
-                                found_at: i,
-                                pointing_at: None,
-                            });
-                        }
-                        Domain::Space => {
-                            portals.space = Some(Portal {
-                                found_at: i,
-                                pointing_at: None,
-                            });
-                        }
-                        _ => {}
-                    },
-                };
+impl Solver<'_> {
+    /// Walk the branches depth first, the same order the recursion would
+    /// take, while keeping the pending branches on the heap so that long
+    /// headers cannot overflow the stack
+    fn find_solutions(&mut self, root: Branch) {
+        let mut pending = vec![root];
+
+        while let Some(branch) = pending.pop() {
+            self.steps += 1;
+            if self.steps > self.budget {
+                return;
             }
-            Status::Missing => {
-                weight += 1;
-            }
-            _ => {}
-        };
 
-        match q_domain {
-            Some(&q_domain) => match q_domain {
-                Domain::Type => {
-                    if is_type(&config.type_rule, &block, commit) {
-                        q.next();
-                        block.domain = Domain::Type;
-                        if block.status == Status::Unsigned {
-                            block.status = Status::Settled;
-                        }
-                    } else {
-                        try_missing!(i, Val::Seq);
-                        try_misplaced!(i, Val::Seq);
-                        return;
-                    }
+            if let Some(best) = &self.best {
+                if branch.weight >= best.weight {
+                    continue;
                 }
-                Domain::Colon => {
-                    if block.val == Val::Colon {
-                        q.next();
-                        block.domain = Domain::Colon;
-                        if block.status == Status::Unsigned {
-                            block.status = Status::Settled;
-                        }
-                    } else {
-                        try_missing!(i, Val::Colon);
-                        try_misplaced!(i, Val::Colon);
-                        return;
-                    }
+            }
+
+            let seen = (branch.i, branch.expect, branch.portals.shape(), branch.desc);
+            match self.seen.get(&seen) {
+                Some(&weight) if weight <= branch.weight => continue,
+                _ => {
+                    self.seen.insert(seen, branch.weight);
                 }
-                Domain::Space => {
-                    if block.val == Val::Space {
-                        q.next();
-                        block.domain = Domain::Space;
-                        if block.status == Status::Unsigned {
-                            block.status = Status::Settled;
-                        }
-                    } else {
-                        try_missing!(i, Val::Space);
-                        try_misplaced!(i, Val::Space);
-                        return;
-                    }
+            }
+
+            let block = self
+                .blocks
+                .get(branch.i)
+                .filter(|block| block.val != Val::EOL);
+
+            match block {
+                Some(block) => {
+                    let alts = self.step(branch, *block);
+                    pending.extend(alts.into_iter().rev());
                 }
-                Domain::Desc => {}
-                _ => todo!(),
-            },
-            None => {
-                todo!()
+                None => self.finish(branch),
+            }
+        }
+    }
+
+    /// Every meaningful interpretation of the given input block,
+    /// in the order they are to be tried
+    fn step(&self, branch: Branch, block: Block) -> Vec<Branch> {
+        let mut alts = Vec::new();
+        let expect = branch.expect;
+
+        // the block we were waiting for, connect it to the portal
+        if let Some(Portal::Open(_)) = portal_of(&branch.portals, block.val) {
+            if !branch.desc {
+                let mut alt = branch.clone();
+                alt.connect(block);
+                alts.push(alt);
+            }
+        }
+
+        if expect == Domain::Desc {
+            if branch.desc {
+                let mut alt = branch;
+                alt.extend_desc(block);
+                alts.push(alt);
+            } else if block.val == Val::Space {
+                let mut alt = branch;
+                alt.extra(block);
+                alts.push(alt);
+            } else {
+                let mut alt = branch;
+                alt.extend_desc(block);
+                alts.push(alt);
             }
+            return alts;
+        }
+
+        if self.fits(expect, &block) {
+            let mut alt = branch.clone();
+            alt.settle(block);
+            alts.push(alt);
+        }
+
+        if let Some(Portal::Ref(_)) = portal_of(&branch.portals, val_of(expect)) {
+            // the block has been found earlier, this is its place
+            let mut alt = branch;
+            alt.close(expect);
+            alts.push(alt);
+            return alts;
+        }
+
+        if is_optional(expect) {
+            let mut alt = branch.clone();
+            alt.expect = skip(expect);
+            alts.push(alt);
+        } else {
+            if can_be_misplaced(expect) {
+                let mut alt = branch.clone();
+                alt.open(expect);
+                alts.push(alt);
+            }
+
+            let mut alt = branch.clone();
+            alt.missing(expect);
+            alts.push(alt);
         }
 
-        match block.val {
-            Val::Colon => {
-                if block.status == Status::Unsigned {
-                    match &mut portals.colon {
-                        Some(colon_portal) => {
-                            if colon_portal.is_connected() {
-                                block.status = Status::Extra;
-                                weight += 1;
-                            } else {
-                                colon_portal.pointing_at = Some(i);
-                                block.status = Status::Ref(colon_portal.found_at);
-                                block.domain = Domain::Colon;
-                            }
-                        }
-                        None => {
-                            block.status = Status::Extra;
-                            weight += 1;
-                        }
+        if block.val != Val::Seq {
+            let mut alt = branch.clone();
+            alt.extra(block);
+            alts.push(alt);
+        }
+
+        let domain: Domain = block.val.into();
+        if can_be_misplaced(domain)
+            && is_ahead(domain, expect)
+            && portal_of(&branch.portals, block.val).is_none()
+        {
+            let mut alt = branch;
+            alt.refer(block);
+            alts.push(alt);
+        }
+
+        alts
+    }
+
+    /// There is no more input to look at
+    fn finish(&mut self, branch: Branch) {
+        let mut branch = branch;
+
+        loop {
+            let expect = branch.expect;
+
+            if let Some(Portal::Ref(_)) = portal_of(&branch.portals, val_of(expect)) {
+                branch.close(expect);
+                continue;
+            }
+
+            match expect {
+                Domain::Desc => {
+                    if !branch.desc {
+                        branch.missing(expect);
                     }
+                    break;
                 }
+                _ if is_optional(expect) => branch.expect = skip(expect),
+                _ => branch.missing(expect),
             }
-            _ => {}
-        };
+        }
+
+        if !branch.portals.is_empty() {
+            return;
+        }
+
+        if let Some(best) = &self.best {
+            if branch.weight >= best.weight {
+                return;
+            }
+        }
+
+        for block in self.blocks.iter().skip(branch.i) {
+            branch.solution.push(Block {
+                domain: Domain::None,
+                status: Status::Settled,
+                ..*block
+            });
+        }
+
+        // when we reach here, assume _a_ possible solution found
+        self.best = Some(Candidate {
+            weight: branch.weight,
+            solution: branch.solution,
+        });
+    }
+
+    fn fits(&self, expect: Domain, block: &Block) -> bool {
+        match expect {
+            Domain::Type => is_type(self.config, block, self.commit),
+            Domain::Scope(Scope::Scope) => block.val == Val::Seq,
+            _ => block.val == val_of(expect),
+        }
+    }
+}
+
+impl Branch {
+    /// Take the block from the input as not expected there
+    fn extra(&mut self, block: Block) {
+        self.solution.push(Block {
+            domain: block.val.into(),
+            status: Status::Extra,
+            ..block
+        });
+        self.weight += EXTRA;
+        self.i += 1;
+    }
+
+    fn settle(&mut self, block: Block) {
+        self.solution.push(Block {
+            domain: self.expect,
+            status: Status::Settled,
+            ..block
+        });
+        self.i += 1;
+        self.expect = next(self.expect);
     }
 
-    if let Some(kind) = &portals.kind {
-        if kind.is_connected() {
-            candidate[kind.found_at].status = Status::Portal(Some(kind.pointing_at.unwrap()));
-            portals.kind = None;
+    fn extend_desc(&mut self, block: Block) {
+        let last = self
+            .solution
+            .last_mut()
+            .filter(|last| self.desc && last.domain == Domain::Desc);
+
+        if let Some(last) = last {
+            last.bytes = match (last.bytes, block.bytes) {
+                (Some(a), Some(b)) => Some((a.start(), b.end()).into()),
+                (a, b) => a.or(b),
+            };
+        } else {
+            self.solution.push(Block {
+                val: Val::Seq,
+                domain: Domain::Desc,
+                bytes: block.bytes,
+                status: Status::Settled,
+            });
+            self.desc = true;
         }
+        self.i += 1;
     }
-    if let Some(colon) = &portals.colon {
-        if colon.is_connected() {
-            candidate[colon.found_at].status = Status::Portal(Some(colon.pointing_at.unwrap()));
-            portals.colon = None;
+
+    fn missing(&mut self, domain: Domain) {
+        self.solution.push(Block {
+            val: val_of(domain),
+            domain,
+            bytes: None,
+            status: Status::Missing,
+        });
+        self.weight += MISSING;
+        self.expect = next(domain);
+    }
+
+    /// Mark the place for the block that is expected to be found later
+    fn open(&mut self, domain: Domain) {
+        let i = self.solution.len();
+        self.solution.push(Block {
+            val: val_of(domain),
+            domain,
+            bytes: None,
+            status: Status::Portal(None),
+        });
+        if let Some(portal) = self.portals.get_mut(domain) {
+            *portal = Some(Portal::Open(i));
         }
+        self.weight += MISPLACED;
+        self.expect = next(domain);
     }
-    if let Some(space) = &portals.space {
-        if space.is_connected() {
-            candidate[space.found_at].status = Status::Portal(Some(space.pointing_at.unwrap()));
-            portals.colon = None;
+
+    /// Mark the block found too early, waiting for its place
+    fn refer(&mut self, block: Block) {
+        let domain: Domain = block.val.into();
+        let i = self.solution.len();
+        self.solution.push(Block {
+            domain,
+            status: Status::Ref(0),
+            ..block
+        });
+        if let Some(portal) = self.portals.get_mut(domain) {
+            *portal = Some(Portal::Ref(i));
         }
+        self.i += 1;
     }
 
-    println!("{:#?}", solutions);
+    /// Connect the block found too late with the place it should have been
+    fn connect(&mut self, block: Block) {
+        let domain: Domain = block.val.into();
+        let i = self.solution.len();
 
-    if !portals.is_empty() {
-        return;
+        if let Some(Some(Portal::Open(portal))) = self.portals.get_mut(domain).map(|p| p.take()) {
+            if let Some(open) = self.solution.get_mut(portal) {
+                open.status = Status::Portal(Some(i));
+            }
+            self.solution.push(Block {
+                domain,
+                status: Status::Ref(portal),
+                ..block
+            });
+        }
+        self.i += 1;
     }
 
-    // when we reach here, assume _a_ possible solution found
-    solutions.push(Candidate {
-        weight,
-        solution: candidate,
-    });
+    /// Put the place for the block which was found too early
+    fn close(&mut self, domain: Domain) {
+        let i = self.solution.len();
+
+        if let Some(Some(Portal::Ref(r))) = self.portals.get_mut(domain).map(|p| p.take()) {
+            if let Some(misplaced) = self.solution.get_mut(r) {
+                misplaced.status = Status::Ref(i);
+            }
+            self.solution.push(Block {
+                val: val_of(domain),
+                domain,
+                bytes: None,
+                status: Status::Portal(Some(r)),
+            });
+        }
+        self.weight += MISPLACED;
+        self.expect = next(domain);
+    }
 }
 
-fn is_type(expected_type: &TypeRule, actual_block: &Block, commit: &str) -> bool {
-    match expected_type {
-        TypeRule::AnyFirstSeq => match actual_block.val {
-            Val::Seq => true,
-            _ => false,
-        },
+/// The domain expected after the given one
+fn next(domain: Domain) -> Domain {
+    match domain {
+        Domain::Root => Domain::Type,
+        Domain::Type => Domain::Scope(Scope::OpenBracket),
+        Domain::Scope(Scope::OpenBracket) => Domain::Scope(Scope::Scope),
+        Domain::Scope(Scope::Scope) => Domain::Scope(Scope::CloseBracket),
+        Domain::Scope(Scope::CloseBracket) => Domain::Breaking,
+        Domain::Breaking => Domain::Colon,
+        Domain::Colon => Domain::Space,
+        Domain::Space => Domain::Desc,
+        Domain::Desc | Domain::None => Domain::None,
+    }
+}
+
+/// Blocks of these domains can be omitted entirely
+fn is_optional(domain: Domain) -> bool {
+    matches!(domain, Domain::Scope(Scope::OpenBracket) | Domain::Breaking)
+}
+
+/// The domain expected after the optional one has been omitted
+fn skip(domain: Domain) -> Domain {
+    match domain {
+        // when open bracket is skipped the whole scope is skipped
+        Domain::Scope(Scope::OpenBracket) => Domain::Breaking,
+        _ => next(domain),
+    }
+}
+
+fn can_be_misplaced(domain: Domain) -> bool {
+    matches!(domain, Domain::Colon | Domain::Space)
+}
+
+/// Whether blocks of the `domain` are expected later than `expect`
+fn is_ahead(domain: Domain, expect: Domain) -> bool {
+    let mut cursor = expect;
+    while cursor != Domain::None {
+        cursor = next(cursor);
+        if cursor == domain {
+            return true;
+        }
+    }
+    false
+}
+
+fn val_of(domain: Domain) -> Val {
+    match domain {
+        Domain::Root => Val::Root,
+        Domain::None => Val::None,
+        Domain::Type | Domain::Desc | Domain::Scope(Scope::Scope) => Val::Seq,
+        Domain::Scope(Scope::OpenBracket) => Val::OpenBracket,
+        Domain::Scope(Scope::CloseBracket) => Val::CloseBracket,
+        Domain::Breaking => Val::ExclMark,
+        Domain::Colon => Val::Colon,
+        Domain::Space => Val::Space,
+    }
+}
+
+fn portal_of(portals: &Portals, val: Val) -> Option<Portal> {
+    match val {
+        Val::Colon => portals.colon,
+        Val::Space => portals.space,
+        _ => None,
+    }
+}
+
+/// Whether the block is the type, the aliases are the types they stand for
+fn is_type(config: &Config, actual_block: &Block, commit: &str) -> bool {
+    match &config.type_rule {
+        TypeRule::AnyFirstSeq => actual_block.val == Val::Seq,
         TypeRule::Strict(set) => match (actual_block.domain, actual_block.val) {
             (Domain::Type, _) => true,
             (_, Val::Seq) => match actual_block.capture(commit) {
-                Some(val) => set.get(val).is_some(),
+                Some(val) => set.contains(config.canonical(val)),
                 None => false,
             },
             _ => false,
         },
-        TypeRule::Like(_) => todo!(),
+        // turned down before the analysis starts, see [`analyze_header`]
+        TypeRule::Like(_) => false,
     }
 }
 
@@ -294,8 +563,8 @@ mod tests {
     #[inline]
     fn with_commit(config: &Config, commit: &str) -> Vec<Block> {
         println!("commit {:?}", commit);
-        let w = WeakCommit::parse(commit).unwrap();
-        analyze_header(commit, &config, w.header)
+        let w = WeakCommit::parse(commit);
+        analyze_header(commit, config, w.header).unwrap()
     }
 
     #[test]
@@ -303,6 +572,7 @@ mod tests {
         let blocks = with_commit(
             &Config {
                 type_rule: TypeRule::Strict(HashSet::from_iter(["fix".to_string()])),
+                ..Config::default()
             },
             " :not me",
         );
@@ -321,6 +591,7 @@ mod tests {
         let blocks = with_commit(
             &Config {
                 type_rule: TypeRule::Strict(HashSet::from_iter(["fix".to_string()])),
+                ..Config::default()
             },
             " :fix me",
         );
@@ -339,6 +610,7 @@ mod tests {
         let blocks = with_commit(
             &Config {
                 type_rule: TypeRule::AnyFirstSeq,
+                ..Config::default()
             },
             "one two three",
         );
@@ -357,6 +629,7 @@ mod tests {
         let blocks = with_commit(
             &Config {
                 type_rule: TypeRule::Strict(HashSet::from_iter(["fix".to_string()])),
+                ..Config::default()
             },
             "fix two three",
         );
@@ -375,6 +648,7 @@ mod tests {
         let blocks = with_commit(
             &Config {
                 type_rule: TypeRule::Strict(HashSet::from_iter(["fix".to_string()])),
+                ..Config::default()
             },
             "one two three",
         );
@@ -387,4 +661,34 @@ mod tests {
 
         assert_eq!(f.blocks, blocks[..f.end_blocks]);
     }
+
+    #[test]
+    fn like() {
+        let config = Config {
+            type_rule: TypeRule::Like(HashSet::from_iter(["fix".to_string()])),
+            ..Config::default()
+        };
+        let w = WeakCommit::parse("fix: me");
+        assert_eq!(
+            analyze_header("fix: me", &config, w.header),
+            Err(Error::Unsupported("`TypeRule::Like`"))
+        );
+    }
+
+    #[test]
+    fn long_header() {
+        let commit = "a(".repeat(20_000);
+        let w = WeakCommit::parse(&commit);
+        assert!(analyze_header(&commit, &Config::default(), w.header).is_ok());
+    }
+
+    #[test]
+    fn budget() {
+        let commit = "fix(app)!: me";
+        let w = WeakCommit::parse(commit);
+        assert_eq!(
+            solve(commit, &Config::default(), w.header, 10),
+            Err(Error::BudgetExceeded(10))
+        );
+    }
 }
@@ -0,0 +1,152 @@
+use crate::{
+    analyze::analyze,
+    block::Status,
+    config::Config,
+    domain::{Domain, Scope},
+    error::Error,
+    glob,
+    header_issue::header,
+    report::Report,
+};
+
+/// Analyse the commit the same way [`analyze`] does, then check its scope
+/// against the scopes of the changed paths, e.g. the staged ones, mapped with
+/// the path globs from the config
+///
+/// ```
+/// use sven::{analyze_paths, header, Config, Domain, Scope};
+///
+/// let config = Config::parse(r#"paths = { "packages/app/**" = "app" }"#).unwrap();
+///
+/// let report = analyze_paths("fix: me", &config, &["packages/app/lib.rs"]).unwrap();
+/// assert_eq!(report.header, vec![header::Issue::Missing(Domain::Scope(Scope::Scope), 3)]);
+/// assert_eq!(report.changed_scopes, vec!["app".to_string()]);
+/// ```
+pub fn analyze_paths(commit: &str, config: &Config, paths: &[&str]) -> Result<Report, Error> {
+    let mut report = analyze(commit, config)?;
+    if config.scope_paths.is_empty() {
+        return Ok(report);
+    }
+
+    report.changed_scopes = changed_scopes(config, paths);
+    if report.changed_scopes.is_empty() || !report.header.is_empty() {
+        return Ok(report);
+    }
+
+    let settled = |domain: Domain| {
+        report
+            .solution
+            .iter()
+            .find(|block| block.domain == domain && block.status == Status::Settled)
+    };
+
+    let issue = match settled(Domain::Scope(Scope::Scope)) {
+        Some(scope) => match (scope.capture(commit), scope.bytes) {
+            (Some(val), _) if report.changed_scopes.iter().any(|s| s == val) => None,
+            (_, Some(bytes)) => Some(header::Issue::Mismatch(scope.domain, bytes)),
+            _ => None,
+        },
+        None => settled(Domain::Type)
+            .and_then(|kind| kind.bytes)
+            .map(|bytes| header::Issue::Missing(Domain::Scope(Scope::Scope), bytes.end())),
+    };
+    report.header.extend(issue);
+
+    Ok(report)
+}
+
+/// Scopes of the paths in the order they are first seen, each path taking
+/// the scope of the first glob it matches in the order of the config, and
+/// the paths matching none of the globs left out
+fn changed_scopes(config: &Config, paths: &[&str]) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for path in paths {
+        let scope = config
+            .scope_paths
+            .iter()
+            .find(|(glob, _)| glob::matches(glob, path))
+            .map(|(_, scope)| scope);
+        if let Some(scope) = scope.filter(|scope| !scopes.contains(scope)) {
+            scopes.push(scope.clone());
+        }
+    }
+
+    scopes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytes::Bytes;
+    use pretty_assertions::assert_eq;
+
+    fn config() -> Config {
+        Config::parse(
+            r#"
+[paths]
+"packages/app/**" = "app"
+"packages/ui/**" = "ui"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn matching_scope() {
+        let paths = ["packages/app/lib.rs", "packages/ui/lib.rs", "README.md"];
+        let report = analyze_paths("fix(ui): me", &config(), &paths).unwrap();
+        assert_eq!(report.header, vec![]);
+        assert_eq!(report.changed_scopes, vec!["app", "ui"]);
+    }
+
+    #[test]
+    fn mismatching_scope() {
+        let report = analyze_paths("fix(ui): me", &config(), &["packages/app/lib.rs"]).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Mismatch(
+                Domain::Scope(Scope::Scope),
+                Bytes::new(4, 6)
+            )]
+        );
+    }
+
+    #[test]
+    fn overlapping_globs() {
+        let config = Config::parse(
+            r#"
+[paths]
+"packages/ui/**" = "ui"
+"packages/**" = "packages"
+"#,
+        )
+        .unwrap();
+        let paths = [
+            "packages/ui/lib.rs",
+            "packages/app/lib.rs",
+            "packages/ui/mod.rs",
+        ];
+        let report = analyze_paths("fix(ui): me", &config, &paths).unwrap();
+        assert_eq!(report.changed_scopes, vec!["ui", "packages"]);
+
+        let paths = ["packages/app/lib.rs", "packages/ui/lib.rs"];
+        let report = analyze_paths("fix(ui): me", &config, &paths).unwrap();
+        assert_eq!(report.changed_scopes, vec!["packages", "ui"]);
+    }
+
+    #[test]
+    fn unrelated_paths() {
+        let report = analyze_paths("fix(ui): me", &config(), &["README.md"]).unwrap();
+        assert!(report.is_ok());
+        assert!(report.changed_scopes.is_empty());
+    }
+
+    #[test]
+    fn header_issues_first() {
+        let report = analyze_paths("fix me", &config(), &["packages/app/lib.rs"]).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Missing(Domain::Colon, 3)]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    analyze::analyze_first_row, config::Config, error::Error, report::Report,
+    weak_commit::WeakCommit,
+};
+
+/// Analyse the header on its own, e.g. the title of the pull request which
+/// becomes the header once squashed. Nothing is cleaned up, and unlike the
+/// commit the title can not span several lines, apart from a single line
+/// ending at the very end, e.g. the one `echo` leaves.
+///
+/// ```
+/// use sven::{analyze_title, Config, Error};
+///
+/// assert!(analyze_title("feat(ui): log in", &Config::default()).unwrap().is_ok());
+/// assert_eq!(
+///     analyze_title("feat: log in\n\nBody", &Config::default()).unwrap_err(),
+///     Error::Multiline(12)
+/// );
+/// ```
+pub fn analyze_title(title: &str, config: &Config) -> Result<Report, Error> {
+    let title = title
+        .strip_suffix("\r\n")
+        .or_else(|| title.strip_suffix('\n'))
+        .unwrap_or(title);
+    if let Some(at) = title.find(['\n', '\r']) {
+        return Err(Error::Multiline(at));
+    }
+    if title.trim().is_empty() {
+        return Err(Error::Empty);
+    }
+
+    let weak_commit = WeakCommit::parse(title);
+    let (solution, header) = analyze_first_row(title, config, &weak_commit)?;
+
+    Ok(Report {
+        header,
+        solution,
+        ..Report::default()
+    })
+}
+
+#[cfg(test)]
+mod titles {
+    use super::*;
+    use crate::{domain::Domain, header_issue::header};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn header_only() {
+        let config = Config::parse(r#"footers = ["Refs"]"#).unwrap();
+        let report = analyze_title("fix: me", &config).unwrap();
+        assert!(report.is_ok(), "{:?}", report);
+    }
+
+    #[test]
+    fn issues() {
+        let report = analyze_title("fix me", &Config::default()).unwrap();
+        assert_eq!(
+            report.header,
+            vec![header::Issue::Missing(Domain::Colon, 3)]
+        );
+    }
+
+    #[test]
+    fn not_a_single_line() {
+        let config = Config::default();
+        assert_eq!(
+            analyze_title("fix: me\r", &config).unwrap_err(),
+            Error::Multiline(7)
+        );
+        assert_eq!(analyze_title(" ", &config).unwrap_err(), Error::Empty);
+        assert_eq!(
+            analyze_title("fix: me\n\n", &config).unwrap_err(),
+            Error::Multiline(7)
+        );
+    }
+
+    #[test]
+    fn trailing_line_ending() {
+        let config = Config::default();
+        for title in ["fix: me\n", "fix: me\r\n"] {
+            let report = analyze_title(title, &config).unwrap();
+            assert!(report.is_ok(), "{:?}", report);
+        }
+        assert_eq!(analyze_title("\n", &config).unwrap_err(), Error::Empty);
+    }
+}
@@ -12,10 +12,11 @@ pub struct Block {
     pub status: Status,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum Status {
     /// Used for any block, unsigned block means it has not been
     /// processed yet, no unsigned blocks should be used after the analysis
+    #[default]
     Unsigned,
 
     /// Used for any block that has been found to be meaningful in the context of
@@ -38,17 +39,23 @@ pub enum Status {
     /// solution to be useless.
     Misplaced,
 
-    /// Indicates the place where Misplaced block should be
-    Portal,
+    /// Indicates the place where Misplaced block should be, optionally
+    /// pointing at the index of the block that fulfills it
+    Portal(Option<usize>),
+
+    /// Indicates the block that fulfills the Portal at the given index
+    Ref(usize),
 
     /// A simple marker to show that we should be waiting for the block with this status
     /// later
     Promise,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Val {
     Root,
+    #[default]
     None,
     Seq,
     Space,
@@ -62,7 +69,7 @@ pub enum Val {
 impl Block {
     #[inline]
     pub fn capture<'capture>(&self, source: &'capture str) -> Option<&'capture str> {
-        self.bytes.map(|bytes| bytes.capture(source)).flatten()
+        self.bytes.and_then(|bytes| bytes.capture(source))
     }
 
     #[inline]
@@ -76,9 +83,9 @@ impl Block {
     }
 }
 
-impl Into<Domain> for Val {
-    fn into(self) -> Domain {
-        match self {
+impl From<Val> for Domain {
+    fn from(val: Val) -> Self {
+        match val {
             Val::Root => Domain::Root,
             Val::Seq => Domain::Type,
             Val::Colon => Domain::Colon,
@@ -91,15 +98,3 @@ impl Into<Domain> for Val {
         }
     }
 }
-
-impl Default for Val {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
-impl Default for Status {
-    fn default() -> Self {
-        Self::Unsigned
-    }
-}
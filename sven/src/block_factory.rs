@@ -12,14 +12,17 @@ pub struct BlockFactory {
     pub end_byte: usize,
     pub end_blocks: usize,
     pub portals: HashMap<Domain, usize>,
+    pub refs: HashMap<Domain, usize>,
 }
 
+#[allow(dead_code)]
 impl BlockFactory {
     pub fn new() -> Self {
         Self {
             end_byte: 0,
             end_blocks: 1,
             portals: HashMap::new(),
+            refs: HashMap::new(),
             blocks: vec![Block {
                 val: Val::Root,
                 bytes: None,
@@ -30,7 +33,7 @@ impl BlockFactory {
     }
 
     pub fn kind(&mut self, val: &str) -> &mut Self {
-        let val_bytes_len = val.as_bytes().len();
+        let val_bytes_len = val.len();
 
         self.blocks.push(Block {
             val: Val::Seq,
@@ -60,7 +63,7 @@ impl BlockFactory {
     }
 
     pub fn scope_val(&mut self, val: &str) -> &mut Self {
-        let val_bytes_len = val.as_bytes().len();
+        let val_bytes_len = val.len();
 
         self.blocks.push(Block {
             val: Val::Seq,
@@ -120,7 +123,7 @@ impl BlockFactory {
     }
 
     pub fn desc(&mut self, val: &str) -> &mut Self {
-        let val_bytes_len = val.as_bytes().len();
+        let val_bytes_len = val.len();
 
         self.blocks.push(Block {
             val: Val::Seq,
@@ -146,38 +149,51 @@ impl BlockFactory {
         self
     }
 
+    /// The place where the colon should be, connected with
+    /// the `colon_ref` whether it comes before or after
     pub fn colon_misplaced(&mut self) -> &mut Self {
-        let block = Block {
+        let i = self.blocks.len();
+        let to_i = self.refs.remove(&Domain::Colon);
+
+        self.blocks.push(Block {
             val: Val::Colon,
             domain: Domain::Colon,
             bytes: None,
-            status: Status::Portal(None),
-        };
+            status: Status::Portal(to_i),
+        });
 
-        self.blocks.push(block);
-        let i = self.blocks.len() - 1;
+        match to_i {
+            Some(to_i) => self.blocks.get_mut(to_i).unwrap().status = Status::Ref(i),
+            None => {
+                self.portals.insert(Domain::Colon, i);
+            }
+        }
 
-        self.portals.insert(block.domain, i);
         self.end_blocks += 1;
         self
     }
 
+    /// The colon found in the input which belongs
+    /// to where `colon_misplaced` is
     pub fn colon_ref(&mut self) -> &mut Self {
-        let from_i = *self.portals.get(&Domain::Colon).unwrap();
+        let i = self.blocks.len();
+        let from_i = self.portals.remove(&Domain::Colon);
         let bytes = Bytes::single(self.end_byte);
         self.end_byte = bytes.end();
 
-        let block = Block {
+        self.blocks.push(Block {
             val: Val::Colon,
             domain: Domain::Colon,
             bytes: Some(bytes),
-            status: Status::Ref(from_i),
-        };
-
-        self.blocks.push(block);
-        let i = self.blocks.len() - 1;
+            status: Status::Ref(from_i.unwrap_or_default()),
+        });
 
-        self.blocks.get_mut(from_i).unwrap().status = Status::Portal(Some(i));
+        match from_i {
+            Some(from_i) => self.blocks.get_mut(from_i).unwrap().status = Status::Portal(Some(i)),
+            None => {
+                self.refs.insert(Domain::Colon, i);
+            }
+        }
 
         self.end_blocks += 1;
         self
@@ -196,10 +212,13 @@ impl BlockFactory {
     }
 
     pub fn space_extra(&mut self) -> &mut Self {
+        let bytes = Bytes::single(self.end_byte);
+        self.end_byte = bytes.end();
+
         self.blocks.push(Block {
             val: Val::Space,
             domain: Domain::Space,
-            bytes: None,
+            bytes: Some(bytes),
             status: Status::Extra,
         });
 
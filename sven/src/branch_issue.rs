@@ -0,0 +1,53 @@
+pub mod branch {
+    use crate::{
+        bytes::Bytes,
+        message::{Locale, Message},
+    };
+    use std::fmt::Display;
+
+    /// Something that is wrong with the name of the branch, expected to be
+    /// `<type>/<description>`, or `<type>/<scope>-<description>` when the
+    /// scopes are configured
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub enum Issue {
+        /// The name does not start with `<type>/`
+        MissingType,
+
+        /// The type is not one of the allowed ones
+        UnknownType(Bytes),
+
+        /// The word after the type is neither one of the allowed scopes
+        /// nor an issue number
+        UnknownScope(Bytes),
+
+        /// Nothing follows the type, or the scope, expected at the given byte
+        MissingDesc(usize),
+    }
+
+    impl Issue {
+        pub fn bytes(&self) -> Bytes {
+            match self {
+                Issue::MissingType => Bytes::new(0, 0),
+                Issue::UnknownType(bytes) | Issue::UnknownScope(bytes) => *bytes,
+                Issue::MissingDesc(at) => Bytes::new(*at, *at),
+            }
+        }
+
+        /// Message of the issue, to be localised
+        pub fn message(&self) -> Message {
+            Message::new(match self {
+                Issue::MissingType => "branch-missing-type",
+                Issue::UnknownType(_) => "branch-unknown-type",
+                Issue::UnknownScope(_) => "branch-unknown-scope",
+                Issue::MissingDesc(_) => "branch-missing-desc",
+            })
+        }
+    }
+
+    impl Display for Issue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.message().localize(Locale::En))
+        }
+    }
+}
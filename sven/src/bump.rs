@@ -0,0 +1,173 @@
+use crate::{
+    config::Config,
+    conventional_commit::{CommitFooter, ConventionalCommit},
+};
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::Deserialize;
+
+/// Part of the semantic version a commit bumps
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    /// Released without a version change
+    #[default]
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// What the commit bumps: a breaking change is major, `feat` is minor,
+    /// `fix` is patch, and the rest is up to the config
+    ///
+    /// ```
+    /// use sven::{Bump, CommitHeader, Config, ConventionalCommit};
+    ///
+    /// let commit = ConventionalCommit {
+    ///     header: CommitHeader { kind: "feat", scope: None, breaking_change: false, desc: "me" },
+    ///     body: None,
+    ///     footers: &[],
+    /// };
+    /// assert_eq!(Bump::of(&commit, &Config::default()), Bump::Minor);
+    /// ```
+    pub fn of(commit: &ConventionalCommit, config: &Config) -> Self {
+        let breaking_footer = commit
+            .footers
+            .iter()
+            .any(|footer| matches!(footer, CommitFooter::BreakingChange(_)));
+        if commit.header.breaking_change || breaking_footer {
+            return Self::Major;
+        }
+
+        let kind = commit.header.kind;
+        match config.bump.get(kind) {
+            Some(bump) => *bump,
+            None if kind == "feat" => Self::Minor,
+            None if kind == "fix" => Self::Patch,
+            None => Self::None,
+        }
+    }
+
+    /// Next version after this bump. Pre-release and build metadata are
+    /// dropped.
+    ///
+    /// ```
+    /// use semver::Version;
+    /// use sven::Bump;
+    ///
+    /// let version = Version::new(1, 2, 3);
+    /// assert_eq!(Bump::Minor.apply(&version), Version::new(1, 3, 0));
+    /// ```
+    pub fn apply(self, version: &Version) -> Version {
+        let mut next = version.clone();
+        next.pre = Prerelease::EMPTY;
+        next.build = BuildMetadata::EMPTY;
+
+        match self {
+            Self::None => {}
+            Self::Patch => next.patch += 1,
+            Self::Minor => {
+                next.minor += 1;
+                next.patch = 0;
+            }
+            Self::Major => {
+                next.major += 1;
+                next.minor = 0;
+                next.patch = 0;
+            }
+        }
+
+        next
+    }
+
+    /// Same bump, unless the config opts into breaking changes bumping the
+    /// minor during the initial development, until `1.0.0`
+    ///
+    /// ```
+    /// use semver::Version;
+    /// use sven::{Bump, Config};
+    ///
+    /// let config = Config::parse("initial_development = true").unwrap();
+    /// let bump = Bump::Major.within(&Version::new(0, 4, 1), &config);
+    /// assert_eq!(bump, Bump::Minor);
+    /// ```
+    pub fn within(self, version: &Version, config: &Config) -> Self {
+        match self {
+            Self::Major if config.initial_development && version.major == 0 => Self::Minor,
+            bump => bump,
+        }
+    }
+}
+
+#[cfg(test)]
+mod of {
+    use super::*;
+    use crate::conventional_commit::CommitHeader;
+    use pretty_assertions::assert_eq;
+
+    fn commit<'c>(
+        kind: &'c str,
+        breaking_change: bool,
+        footers: &'c [CommitFooter<'c>],
+    ) -> ConventionalCommit<'c> {
+        ConventionalCommit {
+            header: CommitHeader {
+                kind,
+                scope: None,
+                breaking_change,
+                desc: "me",
+            },
+            body: None,
+            footers,
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        let config = Config::default();
+        assert_eq!(Bump::of(&commit("feat", false, &[]), &config), Bump::Minor);
+        assert_eq!(Bump::of(&commit("fix", false, &[]), &config), Bump::Patch);
+        assert_eq!(Bump::of(&commit("docs", false, &[]), &config), Bump::None);
+    }
+
+    #[test]
+    fn breaking_change() {
+        let config = Config::default();
+        let footers = [CommitFooter::BreakingChange("gone")];
+        assert_eq!(Bump::of(&commit("docs", true, &[]), &config), Bump::Major);
+        assert_eq!(
+            Bump::of(&commit("docs", false, &footers), &config),
+            Bump::Major
+        );
+    }
+
+    #[test]
+    fn configured() {
+        let config = Config::parse(r#"bump = { perf = "patch", feat = "patch" }"#).unwrap();
+        assert_eq!(Bump::of(&commit("perf", false, &[]), &config), Bump::Patch);
+        assert_eq!(Bump::of(&commit("feat", false, &[]), &config), Bump::Patch);
+    }
+
+    #[test]
+    fn apply() {
+        let version = Version::parse("1.2.3-rc.1").unwrap();
+        assert_eq!(Bump::None.apply(&version), Version::new(1, 2, 3));
+        assert_eq!(Bump::Patch.apply(&version), Version::new(1, 2, 4));
+        assert_eq!(Bump::Minor.apply(&version), Version::new(1, 3, 0));
+        assert_eq!(Bump::Major.apply(&version), Version::new(2, 0, 0));
+    }
+
+    #[test]
+    fn initial_development() {
+        let version = Version::new(0, 4, 1);
+        assert_eq!(Bump::Major.apply(&version), Version::new(1, 0, 0));
+
+        let config = Config::parse("initial_development = true").unwrap();
+        let bump = Bump::Major.within(&version, &config);
+        assert_eq!(bump.apply(&version), Version::new(0, 5, 0));
+        let released = Version::new(1, 4, 1);
+        assert_eq!(Bump::Major.within(&released, &config), Bump::Major);
+        assert_eq!(Bump::Patch.within(&version, &config), Bump::Patch);
+    }
+}
@@ -1,4 +1,3 @@
-use pest::Span;
 use std::ops::{Range, RangeFrom};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -34,6 +33,12 @@ impl Bytes {
         }
     }
 
+    /// Same amount of bytes, moved further by the given offset
+    #[inline]
+    pub fn shift(&self, by: usize) -> Self {
+        Self::new(self.0 + by, self.1 + by)
+    }
+
     #[inline]
     pub fn start(&self) -> usize {
         self.0
@@ -45,40 +50,33 @@ impl Bytes {
     }
 }
 
-impl Into<Bytes> for (usize, usize) {
+impl From<(usize, usize)> for Bytes {
     #[inline]
-    fn into(self) -> Bytes {
-        Bytes::new(self.0, self.1)
+    fn from((start, end): (usize, usize)) -> Self {
+        Bytes::new(start, end)
     }
 }
 
-impl Into<Bytes> for usize {
+impl From<usize> for Bytes {
     #[inline]
-    fn into(self) -> Bytes {
-        Bytes::new(self, self)
+    fn from(start: usize) -> Self {
+        Bytes::new(start, start)
     }
 }
 
-impl Into<Range<usize>> for Bytes {
+impl From<Bytes> for Range<usize> {
     #[inline]
-    fn into(self) -> Range<usize> {
+    fn from(bytes: Bytes) -> Self {
         Range {
-            start: self.0,
-            end: self.1,
+            start: bytes.0,
+            end: bytes.1,
         }
     }
 }
 
-impl Into<RangeFrom<usize>> for Bytes {
-    #[inline]
-    fn into(self) -> RangeFrom<usize> {
-        RangeFrom { start: self.0 }
-    }
-}
-
-impl Into<Bytes> for Span<'_> {
+impl From<Bytes> for RangeFrom<usize> {
     #[inline]
-    fn into(self) -> Bytes {
-        Bytes::new(self.start(), self.end())
+    fn from(bytes: Bytes) -> Self {
+        RangeFrom { start: bytes.0 }
     }
 }
@@ -0,0 +1,275 @@
+use crate::{
+    config::Config,
+    conventional_commit::{CommitFooter, ConventionalCommit},
+};
+use serde::Deserialize;
+use std::fmt::Write;
+
+/// Section of the changelog listing the commits of the type
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Section {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+}
+
+impl Section {
+    pub fn new(kind: &str, title: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    /// Sections used when there are none in the config
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("feat", "Features"),
+            Self::new("fix", "Bug Fixes"),
+            Self::new("perf", "Performance Improvements"),
+            Self::new("revert", "Reverts"),
+        ]
+    }
+}
+
+/// How the changelog is laid out
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// `## 1.2.0 (2023-01-01)` releases with `*` entries
+    #[default]
+    Markdown,
+
+    /// `## [1.2.0] - 2023-01-01` releases with `-` entries,
+    /// see <https://keepachangelog.com>
+    KeepAChangelog,
+}
+
+const BREAKING_CHANGES: &str = "BREAKING CHANGES";
+
+const KEEP_A_CHANGELOG: &str = "\
+# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+";
+
+/// Changelog of a single release: breaking changes go first, described with
+/// the `BREAKING CHANGE` footer when there is one, then the commits grouped by
+/// the sections from the config. Commits of other types are left out.
+///
+/// ```
+/// use sven::{changelog, CommitHeader, Config, ConventionalCommit, Layout};
+///
+/// let commit = ConventionalCommit {
+///     header: CommitHeader { kind: "feat", scope: Some("app"), breaking_change: false, desc: "me" },
+///     body: None,
+///     footers: &[],
+/// };
+/// let release = changelog("1.0.0", None, &[commit], &Config::default(), Layout::Markdown);
+/// assert_eq!(release, "## 1.0.0\n\n### Features\n\n* **app:** me\n");
+/// ```
+pub fn changelog(
+    version: &str,
+    date: Option<&str>,
+    commits: &[ConventionalCommit],
+    config: &Config,
+    layout: Layout,
+) -> String {
+    let mut t = String::new();
+    let bullet = match layout {
+        Layout::Markdown => '*',
+        Layout::KeepAChangelog => '-',
+    };
+
+    match (layout, date) {
+        (Layout::Markdown, Some(date)) => writeln!(t, "## {} ({})", version, date),
+        (Layout::Markdown, None) => writeln!(t, "## {}", version),
+        (Layout::KeepAChangelog, Some(date)) => writeln!(t, "## [{}] - {}", version, date),
+        (Layout::KeepAChangelog, None) => writeln!(t, "## [{}]", version),
+    }
+    .expect("writing to a string");
+
+    let breaking: Vec<_> = commits
+        .iter()
+        .flat_map(|commit| {
+            let footers: Vec<_> = commit
+                .footers
+                .iter()
+                .filter_map(|footer| match footer {
+                    CommitFooter::BreakingChange(value) => Some(*value),
+                    _ => None,
+                })
+                .collect();
+            match footers.is_empty() && commit.header.breaking_change {
+                true => vec![(commit.header.scope, commit.header.desc)],
+                false => footers
+                    .into_iter()
+                    .map(|value| (commit.header.scope, value))
+                    .collect(),
+            }
+        })
+        .collect();
+    section(&mut t, bullet, BREAKING_CHANGES, &breaking);
+
+    for s in &config.changelog {
+        let entries: Vec<_> = commits
+            .iter()
+            .filter(|commit| commit.header.kind == s.kind)
+            .map(|commit| (commit.header.scope, commit.header.desc))
+            .collect();
+        section(&mut t, bullet, &s.title, &entries);
+    }
+
+    t
+}
+
+fn section(t: &mut String, bullet: char, title: &str, entries: &[(Option<&str>, &str)]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    write!(t, "\n### {}\n\n", title).expect("writing to a string");
+    for (scope, desc) in entries {
+        // footer values may span several lines, keep them within the entry
+        let desc = desc.lines().map(str::trim).collect::<Vec<_>>().join("\n  ");
+        match scope {
+            Some(scope) => writeln!(t, "{} **{}:** {}", bullet, scope, desc),
+            None => writeln!(t, "{} {}", bullet, desc),
+        }
+        .expect("writing to a string");
+    }
+}
+
+/// Put the release on top of the existing changelog, right before the
+/// previous release and after the preamble, if any
+///
+/// ```
+/// use sven::{prepend, Layout};
+///
+/// let changelog = prepend("# Changelog\n\n## 1.0.0\n", "## 1.1.0\n", Layout::Markdown);
+/// assert_eq!(changelog, "# Changelog\n\n## 1.1.0\n\n## 1.0.0\n");
+/// ```
+pub fn prepend(changelog: &str, release: &str, layout: Layout) -> String {
+    let changelog = match (changelog.trim().is_empty(), layout) {
+        (true, Layout::KeepAChangelog) => KEEP_A_CHANGELOG,
+        _ => changelog,
+    };
+
+    let at = changelog
+        .match_indices("## ")
+        .map(|(at, _)| at)
+        .find(|at| *at == 0 || changelog[..*at].ends_with('\n'));
+
+    match at {
+        Some(at) => format!("{}{}\n{}", &changelog[..at], release, &changelog[at..]),
+        None if changelog.trim().is_empty() => release.to_string(),
+        None => format!("{}\n\n{}", changelog.trim_end(), release),
+    }
+}
+
+#[cfg(test)]
+mod release {
+    use super::*;
+    use crate::conventional_commit::CommitHeader;
+    use pretty_assertions::assert_eq;
+
+    fn commit<'c>(
+        kind: &'c str,
+        scope: Option<&'c str>,
+        breaking_change: bool,
+        desc: &'c str,
+        footers: &'c [CommitFooter<'c>],
+    ) -> ConventionalCommit<'c> {
+        ConventionalCommit {
+            header: CommitHeader {
+                kind,
+                scope,
+                breaking_change,
+                desc,
+            },
+            body: None,
+            footers,
+        }
+    }
+
+    #[test]
+    fn sections() {
+        let footers = [CommitFooter::BreakingChange("config is gone")];
+        let commits = [
+            commit("fix", None, false, "one", &[]),
+            commit("feat", Some("app"), false, "two", &footers),
+            commit("docs", None, false, "three", &[]),
+            commit("feat", None, true, "four", &[]),
+        ];
+        let actual = changelog(
+            "1.0.0",
+            Some("2023-01-01"),
+            &commits,
+            &Config::default(),
+            Layout::Markdown,
+        );
+        let expected = "\
+## 1.0.0 (2023-01-01)
+
+### BREAKING CHANGES
+
+* **app:** config is gone
+* four
+
+### Features
+
+* **app:** two
+* four
+
+### Bug Fixes
+
+* one
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn configured_sections() {
+        let config = Config::parse(
+            r#"changelog = [{ type = "fix", title = "Fixed" }, { type = "feat", title = "Added" }]"#,
+        )
+        .unwrap();
+        let commits = [
+            commit("feat", None, false, "one", &[]),
+            commit("fix", None, false, "two", &[]),
+        ];
+        let actual = changelog("1.0.0", None, &commits, &config, Layout::KeepAChangelog);
+        let expected = "\
+## [1.0.0]
+
+### Fixed
+
+- two
+
+### Added
+
+- one
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn prepend_to_empty() {
+        assert_eq!(prepend("", "## 1.0.0\n", Layout::Markdown), "## 1.0.0\n");
+        assert_eq!(
+            prepend("", "## [1.0.0]\n", Layout::KeepAChangelog),
+            format!("{}\n## [1.0.0]\n", KEEP_A_CHANGELOG)
+        );
+    }
+
+    #[test]
+    fn prepend_after_preamble() {
+        assert_eq!(
+            prepend("# Changelog\n", "## 1.0.0\n", Layout::Markdown),
+            "# Changelog\n\n## 1.0.0\n"
+        );
+    }
+}
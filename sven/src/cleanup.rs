@@ -0,0 +1,271 @@
+use crate::bytes::Bytes;
+use serde::Deserialize;
+
+/// Default comment char, see git's `core.commentChar`
+pub const COMMENT: char = '#';
+
+/// Text of the line which separates the commit message from the diff
+/// shown by `git commit -v`, without the comment char
+const SCISSORS: &str = " ------------------------ >8 ------------------------";
+
+/// How the commit message is cleaned up before the analysis, the same as
+/// git's `commit.cleanup` does
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cleanup {
+    /// Strip leading and trailing empty lines, trailing whitespace, comments
+    /// and collapse consecutive empty lines. As in `commit -v` everything from
+    /// the scissors line onwards is removed too.
+    #[default]
+    Strip,
+
+    /// Same as `Strip` except comments are not removed
+    Whitespace,
+
+    /// Same as `Whitespace` except everything from the scissors line onwards
+    /// is removed
+    Scissors,
+
+    /// Do not change the message at all, apart from the line endings
+    Verbatim,
+}
+
+impl Cleanup {
+    /// Name of the mode, the way git's `--cleanup` takes it
+    ///
+    /// ```
+    /// use sven::Cleanup;
+    ///
+    /// assert_eq!(Cleanup::Scissors.as_str(), "scissors");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Cleanup::Strip => "strip",
+            Cleanup::Whitespace => "whitespace",
+            Cleanup::Scissors => "scissors",
+            Cleanup::Verbatim => "verbatim",
+        }
+    }
+}
+
+/// Commit message after the cleanup, which remembers where every byte of it
+/// is located in the original message
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cleaned {
+    pub text: String,
+
+    /// Every piece of the text taken from the original as
+    /// (start in the text, start in the original)
+    segments: Vec<(usize, usize)>,
+
+    original_len: usize,
+}
+
+impl Cleaned {
+    /// Clean up the commit message, line endings of any kind (`\r\n`,
+    /// `\r`) become `\n` regardless of the mode
+    ///
+    /// ```
+    /// use sven::{Cleaned, Cleanup};
+    ///
+    /// let cleaned = Cleaned::new("\n# comment\nfix: me  \n\n\n", Cleanup::Strip, '#');
+    /// assert_eq!(cleaned.text, "fix: me\n");
+    /// assert_eq!(cleaned.original(0), 11);
+    /// ```
+    pub fn new(commit: &str, mode: Cleanup, comment: char) -> Self {
+        let mut cleaned = Self {
+            text: String::with_capacity(commit.len()),
+            segments: Vec::new(),
+            original_len: commit.len(),
+        };
+
+        if mode == Cleanup::Verbatim {
+            for (start, line, eol) in lines(commit) {
+                cleaned.push(line, start);
+                if !eol.is_empty() {
+                    cleaned.push("\n", start + line.len());
+                }
+            }
+            return cleaned;
+        }
+
+        let commit = match mode {
+            Cleanup::Strip | Cleanup::Scissors => &commit[..scissors(commit, comment)],
+            _ => commit,
+        };
+
+        let mut empties: Option<usize> = None;
+
+        for (line_start, line, _) in lines(commit) {
+            if mode == Cleanup::Strip && line.starts_with(comment) {
+                continue;
+            }
+
+            let content = line.trim_end_matches(|c: char| c.is_ascii_whitespace());
+            if content.is_empty() {
+                empties = empties.or(Some(line_start));
+                continue;
+            }
+
+            if let Some(empty) = empties.take() {
+                if !cleaned.text.is_empty() {
+                    cleaned.push("\n", empty);
+                }
+            }
+
+            cleaned.push(content, line_start);
+            cleaned.push("\n", line_start + content.len());
+        }
+
+        cleaned
+    }
+
+    fn push(&mut self, val: &str, original: usize) {
+        match self.segments.last() {
+            // continues the previous segment in the original as well
+            Some(&(at, from)) if original == from + (self.text.len() - at) => {}
+            _ => self.segments.push((self.text.len(), original)),
+        }
+        self.text.push_str(val);
+    }
+
+    /// Byte in the original message for the byte of the text
+    pub fn original(&self, at: usize) -> usize {
+        let i = self.segments.partition_point(|&(start, _)| start <= at);
+        match i.checked_sub(1).map(|i| self.segments[i]) {
+            Some((start, from)) => (from + at - start).min(self.original_len),
+            None => at.min(self.original_len),
+        }
+    }
+
+    /// Bytes in the original message for the bytes of the text
+    pub fn original_bytes(&self, bytes: Bytes) -> Bytes {
+        let start = self.original(bytes.start());
+        match bytes.total() {
+            0 => Bytes::empty_at(start),
+            // the line ending added by the cleanup is not in the original
+            _ => Bytes::new(
+                start,
+                (self.original(bytes.end() - 1) + 1).clamp(start, self.original_len),
+            ),
+        }
+    }
+}
+
+/// Where the scissors line starts, or the end of the commit if there is none
+fn scissors(commit: &str, comment: char) -> usize {
+    lines(commit)
+        .find(|(_, line, _)| line.strip_prefix(comment) == Some(SCISSORS))
+        .map_or(commit.len(), |(start, _, _)| start)
+}
+
+/// Lines of the text as (start, line, line ending), where the line ending is
+/// one of `\n`, `\r\n` or `\r`, and is empty for the last line without it
+pub(crate) fn lines(text: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let rest = &text[start..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let (len, eol) = match rest.find(['\n', '\r']) {
+            Some(at) if rest[at..].starts_with("\r\n") => (at, 2),
+            Some(at) => (at, 1),
+            None => (rest.len(), 0),
+        };
+        let line = (start, &rest[..len], &rest[len..len + eol]);
+        start += len + eol;
+        Some(line)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const MESSAGE: &str = "\n\nfix: me \n\n\n# comment\nbody\t\n\n";
+
+    #[test]
+    fn strip() {
+        let cleaned = Cleaned::new(MESSAGE, Cleanup::Strip, COMMENT);
+        assert_eq!(cleaned.text, "fix: me\n\nbody\n");
+    }
+
+    #[test]
+    fn whitespace() {
+        let cleaned = Cleaned::new(MESSAGE, Cleanup::Whitespace, COMMENT);
+        assert_eq!(cleaned.text, "fix: me\n\n# comment\nbody\n");
+    }
+
+    #[test]
+    fn verbatim() {
+        let cleaned = Cleaned::new(MESSAGE, Cleanup::Verbatim, COMMENT);
+        assert_eq!(cleaned.text, MESSAGE);
+        assert_eq!(cleaned.original(5), 5);
+    }
+
+    #[test]
+    fn scissors() {
+        let message = "fix: me\n# comment\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!(
+            Cleaned::new(message, Cleanup::Scissors, COMMENT).text,
+            "fix: me\n# comment\n"
+        );
+        assert_eq!(
+            Cleaned::new(message, Cleanup::Strip, COMMENT).text,
+            "fix: me\n"
+        );
+        assert_eq!(
+            Cleaned::new(message, Cleanup::Whitespace, COMMENT).text,
+            "fix: me\n# comment\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n"
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        let message = "fix: me\r\n\r\nbody\rmore \r\n";
+        assert_eq!(
+            Cleaned::new(message, Cleanup::Strip, COMMENT).text,
+            "fix: me\n\nbody\nmore\n"
+        );
+
+        let cleaned = Cleaned::new(message, Cleanup::Verbatim, COMMENT);
+        assert_eq!(cleaned.text, "fix: me\n\nbody\nmore \n");
+        assert_eq!(cleaned.original(9), 11);
+        assert_eq!(cleaned.original(14), 16);
+    }
+
+    #[test]
+    fn comment_char() {
+        let cleaned = Cleaned::new("fix: me\n; comment\n# not a comment\n", Cleanup::Strip, ';');
+        assert_eq!(cleaned.text, "fix: me\n# not a comment\n");
+    }
+
+    #[test]
+    fn original() {
+        let cleaned = Cleaned::new(MESSAGE, Cleanup::Strip, COMMENT);
+        // "fix: me"
+        assert_eq!(cleaned.original(0), 2);
+        assert_eq!(cleaned.original(6), 8);
+        // "body"
+        assert_eq!(cleaned.original(9), 23);
+        assert_eq!(
+            cleaned.original_bytes(Bytes::new(9, 13)),
+            Bytes::new(23, 27)
+        );
+        assert_eq!(
+            cleaned.original_bytes(Bytes::empty_at(7)),
+            Bytes::empty_at(9)
+        );
+    }
+
+    #[test]
+    fn original_utf8() {
+        let message = "# коммент\nfix: да\n";
+        let cleaned = Cleaned::new(message, Cleanup::Strip, COMMENT);
+        let bytes = cleaned.original_bytes(Bytes::new(5, 9));
+        assert_eq!(bytes.capture(message), Some("да"));
+    }
+}
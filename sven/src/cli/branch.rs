@@ -0,0 +1,35 @@
+use super::{
+    git,
+    render::{self, Format},
+};
+use std::process::ExitCode;
+use sven::{analyze_branch, Arg, Config, Message};
+
+/// Analyze the name of the branch, or of the current one
+pub fn branch(config: &Config, name: Option<&str>) -> ExitCode {
+    let locale = config.locale();
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => match git::git(&["branch", "--show-current"]) {
+            Ok(name) if !name.trim().is_empty() => name.trim().to_string(),
+            Ok(_) => {
+                let detached = Message::new("branch-detached").localize(locale);
+                let message = Message::new("branch-analyze").arg("error", Arg::Text(detached));
+                eprintln!("{}", message.localize(locale));
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("{}", render::failure("branch-analyze", &e, locale));
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let issues = analyze_branch(&name, config);
+    render::print(&render::branch_issues(&issues, locale), Format::Text);
+
+    match issues.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
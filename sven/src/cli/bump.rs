@@ -0,0 +1,172 @@
+use super::{git, render};
+use anyhow::Result;
+use semver::Version;
+use std::process::ExitCode;
+use sven::{analyze, cancel_reverts, Bump, Config, ConventionalCommit, Trailers, SEPARATORS};
+
+/// Print the version the commits since the latest release tag call for
+pub fn bump(config: &Config, range: Option<&str>) -> ExitCode {
+    match next_version(config, range) {
+        Ok(version) => {
+            println!("{}", version);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", render::failure("bump-failed", &e, config.locale()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Version of the end of the range, HEAD by default: the release tag of
+/// the end when there is one, otherwise the latest release tag before the
+/// end bumped by the commits of the range, or since that tag
+pub(super) fn next_version(config: &Config, range: Option<&str>) -> Result<String> {
+    let end = range.map_or("HEAD", range_end);
+    if let Some((tag, _)) = git::tag_at(end)? {
+        return Ok(tag);
+    }
+
+    let (prefix, version, since) = match git::latest_tag(end)? {
+        Some((tag, version)) => {
+            let prefix = if tag.starts_with('v') { "v" } else { "" };
+            (prefix, version, format!("{}..{}", tag, end))
+        }
+        None => ("", Version::new(0, 0, 0), end.to_string()),
+    };
+
+    let log = git::commits(range.unwrap_or(&since))?;
+    let bump = bump_of(&log, config).within(&version, config);
+
+    Ok(format!("{}{}", prefix, bump.apply(&version)))
+}
+
+/// Revision the range ends with, HEAD when it is open-ended, e.g. `v1.0.0..`
+fn range_end(range: &str) -> &str {
+    let end = range
+        .rsplit_once("..")
+        .map_or(range, |(_, end)| end.trim_start_matches('.'));
+    match end {
+        "" => "HEAD",
+        end => end,
+    }
+}
+
+/// Trailers of the messages of the log, which its commits borrow the footers from
+pub(super) fn trailers<'l>(
+    log: &'l [(String, String)],
+    config: &Config,
+) -> Vec<Option<Trailers<'l>>> {
+    log.iter()
+        .map(|(_, message)| Trailers::parse(message, SEPARATORS, config.comment_char))
+        .collect()
+}
+
+/// Conventional commits of the log along with their SHAs, leaving
+/// the other commits out, including the ones that cannot be analysed
+/// at all, e.g. with the empty message
+pub(super) fn conventional<'l>(
+    log: &'l [(String, String)],
+    trailers: &'l [Option<Trailers<'l>>],
+    config: &'l Config,
+) -> Vec<(&'l str, ConventionalCommit<'l>)> {
+    let mut commits = Vec::new();
+    for ((sha, message), trailers) in log.iter().zip(trailers) {
+        let Ok(report) = analyze(message, config) else {
+            continue;
+        };
+        if let Some(mut commit) =
+            ConventionalCommit::from_report(message, &report, trailers.as_ref())
+        {
+            // the aliases bump and go to the changelog as the types they stand for
+            commit.header = commit.header.canonical(config);
+            commits.push((sha.as_str(), commit));
+        }
+    }
+    commits
+}
+
+/// What the commits of the log bump, the reverted ones and
+/// their reverts aside
+fn bump_of(log: &[(String, String)], config: &Config) -> Bump {
+    let trailers = trailers(log, config);
+    let commits = conventional(log, &trailers, config);
+
+    cancel_reverts(&commits)
+        .into_iter()
+        .map(|commit| Bump::of(commit, config))
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod bump_of {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn log(messages: &[(&str, &str)]) -> Vec<(String, String)> {
+        messages
+            .iter()
+            .map(|(sha, message)| (sha.to_string(), message.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn conventional() {
+        let config = Config::default();
+        let feat = log(&[("1a2b3c4d", "feat: me\n")]);
+        assert_eq!(bump_of(&feat, &config), Bump::Minor);
+        let breaking = log(&[("1a2b3c4d", "fix: me\n\nBREAKING CHANGE: gone\n")]);
+        assert_eq!(bump_of(&breaking, &config), Bump::Major);
+    }
+
+    #[test]
+    fn aliases() {
+        let config = Config::parse("[aliases]\n\"✨\" = \"feat\"").unwrap();
+        let commits = log(&[("1a2b3c4d", "✨: me\n")]);
+        assert_eq!(bump_of(&commits, &config), Bump::Minor);
+    }
+
+    #[test]
+    fn not_analysed() {
+        let commits = log(&[("1a2b3c4d", "feat: me\n"), ("5e6f7a8b", "")]);
+        assert_eq!(bump_of(&commits, &Config::default()), Bump::Minor);
+    }
+
+    #[test]
+    fn not_conventional() {
+        let commits = log(&[("1a2b3c4d", "Fix me\n")]);
+        assert_eq!(bump_of(&commits, &Config::default()), Bump::None);
+    }
+
+    #[test]
+    fn reverted() {
+        let commits = log(&[
+            (
+                "5e6f7a8b",
+                "Revert \"feat!: me\"\n\nThis reverts commit 1a2b3c4d.\n",
+            ),
+            ("1a2b3c4d", "feat!: me\n"),
+            ("9c0d1e2f", "fix: you\n"),
+        ]);
+        assert_eq!(bump_of(&commits, &Config::default()), Bump::Patch);
+    }
+}
+
+#[cfg(test)]
+mod range {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn end() {
+        for (range, end) in [
+            ("v1.0.0..v1.1.0", "v1.1.0"),
+            ("v1.0.0...main", "main"),
+            ("v1.0.0..", "HEAD"),
+            ("1a2b3c4d", "1a2b3c4d"),
+        ] {
+            assert_eq!(range_end(range), end, "{}", range);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::{
+    bump::{conventional, next_version, trailers},
+    git, render,
+};
+use anyhow::{bail, Result};
+use std::process::ExitCode;
+use sven::{cancel_reverts, changelog as release, prepend, Config, Layout};
+
+/// File the release is prepended to with `--write`
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Print the changelog of the commits since the latest release tag, or
+/// prepend it to the changelog file
+pub fn changelog(config: &Config, args: &[String]) -> ExitCode {
+    match run(config, args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                render::failure("changelog-failed", &e, config.locale())
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(config: &Config, args: &[String]) -> Result<()> {
+    let mut range = None;
+    let mut layout = Layout::Markdown;
+    let mut write = false;
+    for arg in args {
+        match arg.as_str() {
+            "--keep-a-changelog" => layout = Layout::KeepAChangelog,
+            "-w" | "--write" => write = true,
+            arg if arg.starts_with('-') => bail!("unknown option {}", arg),
+            arg => range = Some(arg),
+        }
+    }
+
+    let version = next_version(config, range)?;
+    // once HEAD is tagged the release is the one of its tag, since the
+    // tag before it, the same way the version is the one of its tag
+    let range = match range {
+        Some(range) => range.to_string(),
+        None => match git::latest_tag_before("HEAD")? {
+            Some((tag, _)) => format!("{}..HEAD", tag),
+            None => "HEAD".to_string(),
+        },
+    };
+
+    let log = git::commits(&range)?;
+    let trailers = trailers(&log, config);
+    let commits = conventional(&log, &trailers, config);
+    // the log goes newest first, while the changelog reads in order
+    let mut commits: Vec<_> = cancel_reverts(&commits).into_iter().cloned().collect();
+    commits.reverse();
+
+    let date = git::git(&["log", "-1", "--format=%cs", &range])?;
+    let date = Some(date.trim()).filter(|date| !date.is_empty());
+    let release = release(&version, date, &commits, config, layout);
+
+    if !write {
+        print!("{}", release);
+        return Ok(());
+    }
+
+    let existing = match std::fs::read_to_string(CHANGELOG_FILE) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    std::fs::write(CHANGELOG_FILE, prepend(&existing, &release, layout))?;
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use super::{
+    git,
+    render::{self, Format},
+};
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+use sven::{
+    analyze_paths, canonicalize, fix, Arg, Config, ConventionalCommit, Error, Message, Report,
+    Trailers, SEPARATORS,
+};
+
+/// Analyze the commit message from the file, or stdin, optionally fixing
+/// what can be fixed first: the file is rewritten, while the fixed message
+/// from stdin is printed to stdout
+pub fn check(config: &Config, path: Option<&str>, autofix: bool) -> ExitCode {
+    let commit = match path {
+        Some(path) => std::fs::read(path),
+        None => {
+            let mut buf = Vec::new();
+            io::stdin().read_to_end(&mut buf).map(|_| buf)
+        }
+    };
+
+    let commit = match commit {
+        Ok(commit) => commit,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                render::failure("check-read", &e.into(), config.locale())
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut commit = match String::from_utf8(commit) {
+        Ok(commit) => commit,
+        Err(e) => {
+            let e = Error::from(e.utf8_error()).into();
+            eprintln!("{}", render::failure("check-analyze", &e, config.locale()));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // scopes are checked against the staged paths, as long as there are any
+    let staged = match config.scope_paths.is_empty() {
+        true => Vec::new(),
+        false => git::staged_paths().unwrap_or_default(),
+    };
+    let staged: Vec<&str> = staged.iter().map(String::as_str).collect();
+
+    let analyze = |commit: &str| -> Option<Report> {
+        match analyze_paths(commit, config, &staged) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    render::failure("check-analyze", &e.into(), config.locale())
+                );
+                None
+            }
+        }
+    };
+
+    let Some(mut report) = analyze(&commit) else {
+        return ExitCode::FAILURE;
+    };
+
+    if autofix {
+        let mut fixed = fix(&commit, &report);
+        if config.rewrite_aliases {
+            let Some(report) = analyze(&fixed) else {
+                return ExitCode::FAILURE;
+            };
+            fixed = canonicalize(&fixed, &report, config);
+        }
+        let written = match path {
+            Some(path) if fixed != commit => std::fs::write(path, &fixed),
+            Some(_) => Ok(()),
+            None => io::stdout().write_all(fixed.as_bytes()),
+        };
+        if let Err(e) = written {
+            eprintln!(
+                "{}",
+                render::failure("check-write", &e.into(), config.locale())
+            );
+            return ExitCode::FAILURE;
+        }
+
+        commit = fixed;
+        report = match analyze(&commit) {
+            Some(report) => report,
+            None => return ExitCode::FAILURE,
+        };
+    }
+
+    let locale = config.locale();
+    render::print(&render::issues(&report, locale), Format::Text);
+
+    let unknown = unknown_reverted(config, &commit, &report);
+    for sha in &unknown {
+        let message = Message::new("check-reverted-not-found").arg("sha", Arg::Text(sha.clone()));
+        eprintln!("{}", message.localize(locale));
+    }
+
+    if !report.is_ok() && !report.changed_scopes.is_empty() {
+        let message = Message::new("check-staged-scopes")
+            .arg("scopes", Arg::Text(report.changed_scopes.join(", ")));
+        eprintln!("{}", message.localize(locale));
+    }
+
+    if report.is_ok() && unknown.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// SHAs of the reverted commits which are not in the repository, as long
+/// as there is one to look in
+fn unknown_reverted(config: &Config, commit: &str, report: &Report) -> Vec<String> {
+    let trailers = Trailers::parse(commit, SEPARATORS, config.comment_char);
+    let Some(revert) = ConventionalCommit::from_report(commit, report, trailers.as_ref()).and_then(
+        |mut commit| {
+            commit.header = commit.header.canonical(config);
+            commit.revert()
+        },
+    ) else {
+        return Vec::new();
+    };
+
+    revert
+        .shas
+        .into_iter()
+        .filter(|sha| git::commit_exists(sha) == Some(false))
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,457 @@
+use super::render;
+use anyhow::{bail, Result};
+use std::{
+    io::{self, BufRead, Write},
+    process::{Command, ExitCode, Stdio},
+};
+use sven::{
+    analyze, Arg, Bytes, CommitFooter, CommitHeader, Config, ConventionalCommit, Domain, Error,
+    Locale, Message, Report, Scope, Status, Trailers, SEPARATORS,
+};
+
+/// Compose the commit interactively, then hand it to `git commit`
+pub fn commit(config: &Config, git_args: &[String]) -> ExitCode {
+    let message = {
+        let stdin = io::stdin();
+        let mut prompt = Prompt {
+            input: stdin.lock(),
+            output: io::stderr(),
+            locale: config.locale(),
+            ended: false,
+        };
+        match compose(config, &mut prompt) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("{}", render::failure("commit-compose", &e, config.locale()));
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+
+    match git_commit(config, &message, git_args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", render::failure("commit-git", &e, config.locale()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Commit the message cleaned up the same way as it was analysed, so that
+/// git stores the message which passed the analysis
+fn git_commit(config: &Config, message: &str, git_args: &[String]) -> Result<bool> {
+    let mut git = Command::new("git")
+        .arg("-c")
+        .arg(format!("core.commentChar={}", config.comment_char))
+        .args(["commit", "-F", "-"])
+        .arg(format!("--cleanup={}", config.cleanup.as_str()))
+        .args(git_args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    git.stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(message.as_bytes())?;
+
+    Ok(git.wait()?.success())
+}
+
+struct Prompt<R, W> {
+    input: R,
+    output: W,
+
+    /// Language of the questions and of the issues
+    locale: Locale,
+
+    /// Whether the input has ended, which ends the lines being asked for
+    ended: bool,
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    /// Text of the message in the language of the developer
+    fn text(&self, message: Message) -> String {
+        message.localize(self.locale)
+    }
+
+    /// Ask for a single line, trimmed
+    fn ask(&mut self, question: Message) -> Result<String> {
+        write!(self.output, "{}: ", self.text(question))?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            bail!(self.text(Message::new("commit-no-more-input")));
+        }
+
+        Ok(line.trim().to_string())
+    }
+
+    /// Ask for lines until the one of a single `.` or the end of the input,
+    /// leaving out the empty lines around them
+    fn ask_lines(&mut self, question: Message) -> Result<Vec<String>> {
+        let question = Message::new("commit-lines").arg("question", Arg::Text(self.text(question)));
+        writeln!(self.output, "{}", self.text(question))?;
+
+        let mut lines: Vec<String> = Vec::new();
+        while !self.ended {
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                self.ended = true;
+                break;
+            }
+            let line = line.trim_end();
+            if line == "." {
+                break;
+            }
+            lines.push(line.to_string());
+        }
+
+        let start = lines.iter().position(|line| !line.is_empty());
+        let end = lines.iter().rposition(|line| !line.is_empty());
+        Ok(match (start, end) {
+            (Some(start), Some(end)) => lines.drain(start..=end).collect(),
+            _ => Vec::new(),
+        })
+    }
+
+    fn reject(&mut self, issues: &[impl std::fmt::Display]) -> Result<()> {
+        for issue in issues {
+            writeln!(self.output, "  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ask for every piece of the commit, validating each with the analysis
+fn compose<R: BufRead, W: Write>(config: &Config, prompt: &mut Prompt<R, W>) -> Result<String> {
+    let question = match config.type_rule.sorted() {
+        Some(types) => Message::new("commit-type-of").arg("types", Arg::Text(types.join(", "))),
+        None => Message::new("commit-type"),
+    };
+    let kind = loop {
+        let kind = prompt.ask(question.clone())?;
+        let piece = (Domain::Type, Bytes::new(0, kind.len()));
+        let issues = header_issues(config, &format!("{}: x", kind), Some(piece))?;
+        match issues.is_empty() && !kind.is_empty() {
+            true => break kind,
+            false => prompt.reject(&issues)?,
+        }
+    };
+
+    let scope = loop {
+        let scope = prompt.ask(Message::new("commit-scope"))?;
+        if scope.is_empty() {
+            break None;
+        }
+        let piece = (
+            Domain::Scope(Scope::Scope),
+            Bytes::new(kind.len() + 1, kind.len() + 1 + scope.len()),
+        );
+        let issues = header_issues(config, &format!("{}({}): x", kind, scope), Some(piece))?;
+        match issues.is_empty() {
+            true => break Some(scope),
+            false => prompt.reject(&issues)?,
+        }
+    };
+
+    let breaking_change = loop {
+        match prompt
+            .ask(Message::new("commit-breaking"))?
+            .to_lowercase()
+            .as_str()
+        {
+            "y" | "yes" => break true,
+            "" | "n" | "no" => break false,
+            _ => prompt.reject(&[prompt.text(Message::new("commit-yes-or-no"))])?,
+        }
+    };
+
+    let desc = loop {
+        let desc = prompt.ask(Message::new("commit-desc"))?;
+        let header = CommitHeader {
+            kind: &kind,
+            scope: scope.as_deref(),
+            desc: &desc,
+            breaking_change,
+        };
+        let issues = header_issues(config, &header.to_string(), None)?;
+        match issues.is_empty() {
+            true => break desc,
+            false => prompt.reject(&issues)?,
+        }
+    };
+
+    let body = prompt.ask_lines(Message::new("commit-body"))?.join("\n");
+
+    let mut footers: Vec<String> = Vec::new();
+    loop {
+        let lines = prompt.ask_lines(Message::new("commit-footers"))?;
+        for footer in lines.into_iter().filter(|line| !line.is_empty()) {
+            match is_footer(config, &footer) {
+                true => footers.push(footer),
+                false => {
+                    let issue =
+                        Message::new("commit-not-a-footer").arg("footer", Arg::Text(footer));
+                    prompt.reject(&[prompt.text(issue)])?
+                }
+            }
+        }
+
+        let message = render(
+            config,
+            &kind,
+            scope.as_deref(),
+            breaking_change,
+            &desc,
+            &body,
+            &footers,
+        );
+        let report = match analysis(&message, config)? {
+            Ok(report) if report.is_ok() => return Ok(message),
+            Ok(report) => report,
+            Err(issue) => {
+                prompt.reject(&[issue])?;
+                if prompt.ended {
+                    bail!(prompt.text(Message::new("commit-no-more-input")));
+                }
+                continue;
+            }
+        };
+
+        let locale = config.locale();
+        let issues: Vec<String> = report
+            .footers
+            .values()
+            .flatten()
+            .map(|issue| issue.message().localize(locale))
+            .chain(
+                report
+                    .header
+                    .iter()
+                    .map(|issue| issue.message().localize(locale)),
+            )
+            .collect();
+        prompt.reject(&issues)?;
+        if prompt.ended {
+            bail!(prompt.text(Message::new("commit-no-more-input")));
+        }
+    }
+}
+
+fn render(
+    config: &Config,
+    kind: &str,
+    scope: Option<&str>,
+    breaking_change: bool,
+    desc: &str,
+    body: &str,
+    footers: &[String],
+) -> String {
+    let footers = format!("x\n\n{}", footers.join("\n"));
+    let footers = parse_footers(config, &footers);
+
+    ConventionalCommit {
+        header: CommitHeader {
+            kind,
+            scope,
+            desc,
+            breaking_change,
+        },
+        body: match body.is_empty() {
+            true => None,
+            false => Some(body),
+        },
+        footers: &footers,
+    }
+    .to_string()
+}
+
+/// Whether the line is a single footer, as it would be found in the trailer block
+fn is_footer(config: &Config, line: &str) -> bool {
+    parse_footers(config, &format!("x\n\n{}", line)).len() == 1
+}
+
+fn parse_footers<'c>(config: &Config, commit: &'c str) -> Vec<CommitFooter<'c>> {
+    Trailers::parse(commit, SEPARATORS, config.comment_char)
+        .map(|trailers| trailers.footers)
+        .unwrap_or_default()
+}
+
+/// Issues of the header, in the language of the developer, along with the
+/// one of the piece being asked for when it does not make the block of the
+/// domain on its own, e.g. `fix(app)!` given as the type
+fn header_issues(
+    config: &Config,
+    header: &str,
+    piece: Option<(Domain, Bytes)>,
+) -> Result<Vec<String>> {
+    let locale = config.locale();
+    let report = match analysis(header, config)? {
+        Ok(report) => report,
+        Err(issue) => return Ok(vec![issue]),
+    };
+    let mut issues: Vec<String> = report
+        .header
+        .iter()
+        .map(|issue| issue.message().localize(locale))
+        .collect();
+
+    if let Some((domain, bytes)) = piece {
+        let word = header[bytes.start()..bytes.end()]
+            .chars()
+            .all(|c| !matches!(c, '(' | ')' | '!' | ':') && !c.is_whitespace());
+        let whole = report.solution.iter().any(|block| {
+            block.domain == domain && block.status == Status::Settled && block.bytes == Some(bytes)
+        });
+        if !(word && whole) {
+            issues.push(Message::new("commit-single-word").localize(locale));
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Report of the analysis, or why the input could not be analysed at all,
+/// e.g. the header taken for a comment, to be asked again. Only the errors
+/// no other input would avoid are returned as such.
+fn analysis(message: &str, config: &Config) -> Result<Result<Report, String>> {
+    match analyze(message, config) {
+        Ok(report) => Ok(Ok(report)),
+        Err(e @ (Error::Unsupported(_) | Error::Invariant(_))) => Err(e.into()),
+        Err(e) => Ok(Err(e.message().localize(config.locale()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use sven::TypeRule;
+
+    fn compose_with(config: &Config, input: &str) -> Result<String> {
+        let mut prompt = Prompt {
+            input: input.as_bytes(),
+            output: Vec::new(),
+            locale: Locale::En,
+            ended: false,
+        };
+        compose(config, &mut prompt)
+    }
+
+    #[test]
+    fn header_only() {
+        let actual = compose_with(&Config::default(), "fix\n\nn\na simple fix\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix: a simple fix\n");
+    }
+
+    #[test]
+    fn everything() {
+        let input = "feat\napp\ny\nnew api\n\nBody\n\nMore of it\n\n.\nRefs: #1\nBREAKING CHANGE: old api is gone\n";
+        let actual = compose_with(&Config::default(), input).unwrap();
+        let expected = r###"
+feat(app)!: new api
+
+Body
+
+More of it
+
+Refs: #1
+BREAKING CHANGE: old api is gone
+"###
+        .trim_start();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unknown_type_is_asked_again() {
+        let config = Config::parse(r#"types = ["fix"]"#).unwrap();
+        let actual = compose_with(&config, "feat\nfix\n\n\nme\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix: me\n");
+    }
+
+    #[test]
+    fn unknown_scope_is_asked_again() {
+        let config = Config::parse(r#"scopes = ["app"]"#).unwrap();
+        let actual = compose_with(&config, "fix\nui\napp\n\nme\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix(app): me\n");
+    }
+
+    #[test]
+    fn required_footers_are_asked_again() {
+        let config = Config::parse(r#"footers = ["Refs"]"#).unwrap();
+        let actual =
+            compose_with(&config, "fix\n\n\nme\n.\nnot a footer\n.\nRefs: #1\n.\n").unwrap();
+        assert_eq!(actual, "fix: me\n\nRefs: #1\n");
+    }
+
+    #[test]
+    fn more_than_the_type_is_asked_again() {
+        let actual = compose_with(&Config::default(), "fix(app)!\nfix\n\nn\nme\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix: me\n");
+    }
+
+    #[test]
+    fn more_than_the_scope_is_asked_again() {
+        let input = "fix\napp)!\nmy app\napp\nn\nme\n.\n.\n";
+        let actual = compose_with(&Config::default(), input).unwrap();
+        assert_eq!(actual, "fix(app): me\n");
+    }
+
+    #[test]
+    fn empty_description_is_asked_again() {
+        let actual = compose_with(&Config::default(), "fix\n\n\n\nme\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix: me\n");
+    }
+
+    #[test]
+    fn comment_is_asked_again() {
+        let actual = compose_with(&Config::default(), "#fix\nfix\n\n\nme\n.\n.\n").unwrap();
+        assert_eq!(actual, "fix: me\n");
+    }
+
+    #[test]
+    fn unsupported_config() {
+        let mut config = Config::default();
+        config.type_rule = TypeRule::Like(["fix".to_string()].into());
+        let actual = compose_with(&config, "fix\n").unwrap_err();
+        assert_eq!(
+            actual.downcast::<Error>().unwrap(),
+            Error::Unsupported("`TypeRule::Like`")
+        );
+    }
+
+    #[test]
+    fn localized() {
+        let mut prompt = Prompt {
+            input: "fix\n\nда\nn\nme\n.\nnot a footer\n.\n".as_bytes(),
+            output: Vec::new(),
+            locale: Locale::Ru,
+            ended: false,
+        };
+        let config = Config::parse(r#"types = ["fix"]"#).unwrap();
+        assert_eq!(compose(&config, &mut prompt).unwrap(), "fix: me\n");
+
+        let output = String::from_utf8(prompt.output).unwrap();
+        assert!(output.starts_with("тип (fix): "), "{}", output);
+        assert!(output.contains("  ожидается y или n\n"), "{}", output);
+        assert!(output.contains("  не футер: not a footer\n"), "{}", output);
+        assert!(
+            output.contains("тело (необязательно) (в конце строка из одной `.`):\n"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn end_of_input() {
+        assert!(compose_with(&Config::default(), "fix\n").is_err());
+
+        let actual = compose_with(&Config::default(), "fix\n\nn\nme\nBody").unwrap();
+        assert_eq!(actual, "fix: me\n\nBody\n");
+
+        let config = Config::parse(r#"footers = ["Refs"]"#).unwrap();
+        assert!(compose_with(&config, "fix\n\nn\nme\n.\n").is_err());
+    }
+}
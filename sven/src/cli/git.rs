@@ -0,0 +1,88 @@
+use anyhow::{bail, Result};
+use semver::Version;
+use std::process::Command;
+
+/// Run git with the args, returning its stdout
+pub fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        bail!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Latest release tag reachable from the revision, along with its version.
+/// Tags are expected to be semantic versions, optionally prefixed with `v`,
+/// and pre-releases are skipped.
+pub fn latest_tag(revision: &str) -> Result<Option<(String, Version)>> {
+    Ok(latest(&git(&["tag", "--merged", revision])?))
+}
+
+/// Latest release tag of the revision itself, see [`latest_tag`]
+pub fn tag_at(revision: &str) -> Result<Option<(String, Version)>> {
+    Ok(latest(&git(&["tag", "--points-at", revision])?))
+}
+
+/// Latest release tag of the commits before the revision, leaving out the
+/// tags of the revision itself, see [`latest_tag`]
+pub fn latest_tag_before(revision: &str) -> Result<Option<(String, Version)>> {
+    Ok(latest(&git(&[
+        "tag",
+        "--merged",
+        revision,
+        "--no-contains",
+        revision,
+    ])?))
+}
+
+fn latest(tags: &str) -> Option<(String, Version)> {
+    tags.lines()
+        .filter_map(|tag| {
+            let version = Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+            version.pre.is_empty().then(|| (tag.to_string(), version))
+        })
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// SHAs and messages of the commits in the range, newest first,
+/// leaving merges out
+pub fn commits(range: &str) -> Result<Vec<(String, String)>> {
+    log(&[range])
+}
+
+/// SHAs and messages of the commits listed by `git log` with the
+/// revisions, newest first, leaving merges out
+pub fn log(revisions: &[&str]) -> Result<Vec<(String, String)>> {
+    let mut args = vec!["log", "--no-merges", "--format=%H%n%B%x00"];
+    args.extend(revisions);
+    let log = git(&args)?;
+
+    Ok(log
+        .split('\0')
+        .filter_map(|commit| commit.trim_start_matches('\n').split_once('\n'))
+        .filter(|(_, message)| !message.trim().is_empty())
+        .map(|(sha, message)| (sha.to_string(), message.to_string()))
+        .collect())
+}
+
+/// Whether the commit exists in the repository, nothing when
+/// there is no repository to look in
+pub fn commit_exists(sha: &str) -> Option<bool> {
+    git(&["rev-parse", "--git-dir"]).ok()?;
+    Some(git(&["cat-file", "-e", &format!("{}^{{commit}}", sha)]).is_ok())
+}
+
+/// Paths of the changes staged in the index
+pub fn staged_paths() -> Result<Vec<String>> {
+    let diff = git(&["diff", "--cached", "--name-only", "-z"])?;
+
+    Ok(diff
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(String::from)
+        .collect())
+}
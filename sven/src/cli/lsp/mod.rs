@@ -0,0 +1,595 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, Write},
+    process::ExitCode,
+};
+use sven::{
+    analyze, canonicalize, fix, header, Arg, Bytes, Config, Encoding, Locale, Message, ScopeRule,
+    TypeRule,
+};
+
+mod position;
+mod transport;
+
+use position::{negotiate, Positions};
+
+/// Types suggested when any type is allowed, the ones of the conventional
+/// commits specification along with the Angular convention
+const TYPES: [&str; 11] = [
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+/// Footers suggested on top of the required ones
+const FOOTERS: [&str; 2] = ["BREAKING CHANGE", "Refs"];
+
+/// JSON-RPC error code of the unknown method
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Run the language server over stdin and stdout until the client asks it
+/// to exit. Commit messages, `COMMIT_EDITMSG` or the documents of the
+/// `git-commit` language, are analysed as they are edited.
+pub fn lsp(config: &Config) -> ExitCode {
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    let mut server = Server::new(config);
+
+    loop {
+        let message = match transport::read(&mut input) {
+            Ok(Some(message)) => message,
+            Ok(None) => return ExitCode::FAILURE,
+            Err(e) => {
+                eprintln!("could not read the message: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if message["method"] == "exit" {
+            return match server.shutdown {
+                true => ExitCode::SUCCESS,
+                false => ExitCode::FAILURE,
+            };
+        }
+
+        if let Err(e) = reply(&mut output, server.handle(&message)) {
+            eprintln!("could not write the message: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+}
+
+fn reply(output: &mut impl Write, messages: Vec<Value>) -> Result<()> {
+    for message in &messages {
+        transport::write(output, message)?;
+    }
+    Ok(())
+}
+
+struct Server<'c> {
+    config: &'c Config,
+
+    /// Text of the open commit messages by their URIs
+    documents: HashMap<String, String>,
+
+    /// What the columns of the positions are counted in, negotiated with
+    /// the client on initialize
+    encoding: Encoding,
+
+    /// Language of the messages of the diagnostics
+    locale: Locale,
+
+    /// Whether the client asked to shut down, the exit is expected next
+    shutdown: bool,
+}
+
+impl<'c> Server<'c> {
+    fn new(config: &'c Config) -> Self {
+        Self {
+            config,
+            documents: HashMap::new(),
+            encoding: Encoding::Utf16,
+            locale: config.locale(),
+            shutdown: false,
+        }
+    }
+
+    /// Messages to send back to the client, the response to the request
+    /// or the notifications caused by the notification
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            // responses from the client are not expected
+            return Vec::new();
+        };
+        let params = &message["params"];
+
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+
+        let response = match self.request(method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, error)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": error },
+            }),
+        };
+        vec![response]
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => {
+                let (name, encoding) = negotiate(&params["capabilities"]);
+                self.encoding = encoding;
+                Ok(json!({
+                    "capabilities": {
+                        "positionEncoding": name,
+                        // the whole document is sent on every change
+                        "textDocumentSync": 1,
+                        "codeActionProvider": true,
+                        "completionProvider": { "triggerCharacters": ["("] },
+                    },
+                    "serverInfo": { "name": "sven", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/codeAction" => Ok(self.code_actions(params)),
+            "textDocument/completion" => Ok(self.completions(params)),
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method {}", method))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let document = &params["textDocument"];
+        let Some(uri) = document["uri"].as_str() else {
+            return Vec::new();
+        };
+
+        match method {
+            "textDocument/didOpen" => {
+                let language = document["languageId"].as_str().unwrap_or_default();
+                let is_commit = uri.ends_with("COMMIT_EDITMSG")
+                    || matches!(language, "git-commit" | "gitcommit");
+                let Some(text) = document["text"].as_str().filter(|_| is_commit) else {
+                    return Vec::new();
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                let text = changes.and_then(|changes| changes.last()?["text"].as_str());
+                match (self.documents.get_mut(uri), text) {
+                    (Some(document), Some(text)) => *document = text.to_string(),
+                    _ => return Vec::new(),
+                }
+            }
+            "textDocument/didClose" => {
+                if self.documents.remove(uri).is_none() {
+                    return Vec::new();
+                }
+            }
+            _ => return Vec::new(),
+        }
+
+        let diagnostics = match self.documents.get(uri) {
+            Some(text) => self.diagnostics(text),
+            None => Vec::new(),
+        };
+        vec![json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        })]
+    }
+
+    /// Every issue of the commit, an empty commit is only being written
+    fn diagnostics(&self, text: &str) -> Vec<Value> {
+        let Ok(report) = analyze(text, self.config) else {
+            return Vec::new();
+        };
+
+        let positions = Positions::new(text, self.encoding);
+        let mut diagnostics: Vec<Value> = report
+            .header
+            .iter()
+            .map(|issue| self.header_diagnostic(&positions, issue))
+            .collect();
+        for issue in &report.shape {
+            let sven::shape::Issue::MixedLineEndings(row) = issue else {
+                continue;
+            };
+            diagnostics.push(row_diagnostic(
+                &positions,
+                *row,
+                issue.message().localize(self.locale),
+            ));
+        }
+        for (row, issues) in &report.footers {
+            for issue in issues {
+                let message = issue.message().localize(self.locale);
+                diagnostics.push(row_diagnostic(&positions, *row, message));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Fix of every issue within the range which can be fixed mechanically,
+    /// along with the fix of all of them at once
+    fn code_actions(&self, params: &Value) -> Value {
+        let Some((uri, text)) = self.document(params) else {
+            return json!([]);
+        };
+        let Ok(report) = analyze(text, self.config) else {
+            return json!([]);
+        };
+
+        let positions = Positions::new(text, self.encoding);
+        let start = positions.offset(&params["range"]["start"]);
+        let end = positions.offset(&params["range"]["end"]);
+        let mut actions = Vec::new();
+
+        for issue in &report.header {
+            let edits = report.edits(issue);
+            let bytes = issue.bytes();
+            if edits.is_empty() || bytes.end() < start || bytes.start() > end {
+                continue;
+            }
+
+            let edits: Vec<Value> = edits
+                .iter()
+                .map(|edit| text_edit(&positions, edit.bytes, edit.text))
+                .collect();
+            let title = Message::new("lsp-fix")
+                .arg("issue", Arg::Text(issue.message().localize(self.locale)));
+            actions.push(json!({
+                "title": title.localize(self.locale),
+                "kind": "quickfix",
+                "diagnostics": [self.header_diagnostic(&positions, issue)],
+                "edit": { "changes": { uri: edits } },
+            }));
+        }
+
+        let mut fixed = fix(text, &report);
+        if self.config.rewrite_aliases {
+            if let Ok(report) = analyze(&fixed, self.config) {
+                fixed = canonicalize(&fixed, &report, self.config);
+            }
+        }
+        if fixed != text {
+            actions.push(json!({
+                "title": Message::new("lsp-fix-all").localize(self.locale),
+                "kind": "source.fixAll",
+                "edit": { "changes": { uri: [text_edit(&positions, Bytes::new(0, text.len()), &fixed)] } },
+            }));
+        }
+
+        Value::Array(actions)
+    }
+
+    /// Types in place of the type, scopes within the brackets, and the
+    /// tokens of the footers at the start of the rows after the header
+    fn completions(&self, params: &Value) -> Value {
+        let Some((_, text)) = self.document(params) else {
+            return json!([]);
+        };
+        let at = Positions::new(text, self.encoding).offset(&params["position"]);
+        let start = text[..at].rfind('\n').map_or(0, |at| at + 1);
+        let prefix = &text[start..at];
+
+        let header = text
+            .split_inclusive('\n')
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len();
+                Some((line_start, line))
+            })
+            .find(|(_, line)| {
+                !line.trim().is_empty() && !line.starts_with(self.config.comment_char)
+            })
+            .map_or(0, |(start, _)| start);
+
+        if start == header || text[start..].trim().is_empty() && start == 0 {
+            if !prefix.contains(['(', ')', '!', ':', ' ']) {
+                return items(self.types(), 14, "");
+            }
+            if let Some((_, scope)) = prefix.split_once('(') {
+                if !scope.contains([')', ':']) {
+                    return items(self.scopes(), 12, "");
+                }
+            }
+            return json!([]);
+        }
+
+        if start > header
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return items(self.footers(), 5, ": ");
+        }
+        json!([])
+    }
+
+    fn header_diagnostic(&self, positions: &Positions, issue: &header::Issue) -> Value {
+        let message = issue.message().localize(self.locale);
+        diagnostic(positions.range(issue.bytes()), message)
+    }
+
+    fn document<'a>(&'a self, params: &'a Value) -> Option<(&'a str, &'a str)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let text = self.documents.get(uri)?;
+        Some((uri, text))
+    }
+
+    fn types(&self) -> BTreeSet<&str> {
+        match &self.config.type_rule {
+            TypeRule::AnyFirstSeq => TYPES.into_iter().collect(),
+            TypeRule::Strict(set) | TypeRule::Like(set) => set.iter().map(String::as_str).collect(),
+        }
+    }
+
+    fn scopes(&self) -> BTreeSet<&str> {
+        let mut scopes: BTreeSet<&str> = match &self.config.scope_rule {
+            ScopeRule::Any => BTreeSet::new(),
+            ScopeRule::Strict(set) => set.iter().map(String::as_str).collect(),
+        };
+        scopes.extend(
+            self.config
+                .scope_paths
+                .iter()
+                .map(|(_, scope)| scope.as_str()),
+        );
+        scopes
+    }
+
+    fn footers(&self) -> BTreeSet<&str> {
+        let mut footers: BTreeSet<&str> = FOOTERS.into_iter().collect();
+        footers.extend(self.config.required_footers.iter().map(String::as_str));
+        footers
+    }
+}
+
+/// Diagnostic of the whole row
+fn row_diagnostic(positions: &Positions, row: usize, message: String) -> Value {
+    diagnostic(positions.range(positions.row(row)), message)
+}
+
+fn diagnostic(range: Value, message: String) -> Value {
+    json!({
+        "range": range,
+        // error
+        "severity": 1,
+        "source": "sven",
+        "message": message,
+    })
+}
+
+fn text_edit(positions: &Positions, bytes: Bytes, new_text: &str) -> Value {
+    json!({ "range": positions.range(bytes), "newText": new_text })
+}
+
+/// Completion items of the kind, see LSP's `CompletionItemKind`
+fn items(labels: BTreeSet<&str>, kind: u8, suffix: &str) -> Value {
+    labels
+        .into_iter()
+        .map(|label| {
+            json!({
+                "label": label,
+                "kind": kind,
+                "insertText": format!("{}{}", label, suffix),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod server {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const URI: &str = "file:///repo/.git/COMMIT_EDITMSG";
+
+    /// Messages of the tests are not to depend on the environment
+    fn english() -> Config {
+        let mut config = Config::default();
+        config.locale = Some(Locale::En);
+        config
+    }
+
+    fn open<'c>(config: &'c Config, text: &str) -> (Server<'c>, Vec<Value>) {
+        let mut server = Server::new(config);
+        let published = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": URI, "languageId": "git-commit", "version": 1, "text": text },
+            },
+        }));
+        (server, published)
+    }
+
+    fn request(server: &mut Server, method: &str, params: Value) -> Value {
+        let response = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }));
+        response[0]["result"].clone()
+    }
+
+    fn labels(items: &Value) -> Vec<&str> {
+        items
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn diagnostics_in_utf16() {
+        let config = english();
+        let (_, published) = open(&config, "fix(приложение)🚀 me\n");
+        let diagnostics = &published[0]["params"]["diagnostics"];
+        assert_eq!(
+            diagnostics[0]["range"],
+            json!({
+                "start": { "line": 0, "character": 15 },
+                "end": { "line": 0, "character": 15 },
+            })
+        );
+        assert_eq!(diagnostics[0]["message"], "missing colon");
+    }
+
+    #[test]
+    fn localized() {
+        let config = Config::parse(r#"locale = "ru""#).unwrap();
+        let (mut server, published) = open(&config, "fix me\n");
+        let diagnostics = &published[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics[0]["message"], "отсутствует двоеточие");
+
+        let actions = request(
+            &mut server,
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 4 } },
+                "context": { "diagnostics": [] },
+            }),
+        );
+        let titles: Vec<&str> = actions
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|action| action["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "Исправить: отсутствует двоеточие",
+                "Исправить всё, что можно исправить"
+            ]
+        );
+    }
+
+    #[test]
+    fn not_a_commit() {
+        let config = english();
+        let mut server = Server::new(&config);
+        let published = server.handle(&json!({
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": "file:///a.rs", "languageId": "rust", "text": "" } },
+        }));
+        assert_eq!(published, Vec::<Value>::new());
+    }
+
+    #[test]
+    fn change_and_close() {
+        let config = english();
+        let (mut server, _) = open(&config, "fix me\n");
+        let published = server.handle(&json!({
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "fix: me\n" }],
+            },
+        }));
+        assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+
+        let published = server.handle(&json!({
+            "method": "textDocument/didClose",
+            "params": { "textDocument": { "uri": URI } },
+        }));
+        assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+        assert!(server.documents.is_empty());
+    }
+
+    #[test]
+    fn code_actions() {
+        let config = english();
+        let (mut server, _) = open(&config, "fix（апп）me\n");
+        let actions = request(
+            &mut server,
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 4 } },
+                "context": { "diagnostics": [] },
+            }),
+        );
+
+        let fix_all = actions.as_array().unwrap().last().unwrap();
+        assert_eq!(
+            fix_all["edit"]["changes"][URI][0]["newText"],
+            "fix(апп): me\n"
+        );
+        assert_eq!(
+            actions[0]["title"],
+            "Fix wrong punctuation character, expected \"(\""
+        );
+        assert_eq!(
+            actions[0]["edit"]["changes"][URI][0]["range"],
+            json!({
+                "start": { "line": 0, "character": 3 },
+                "end": { "line": 0, "character": 4 },
+            })
+        );
+    }
+
+    #[test]
+    fn completions() {
+        let config = Config::parse(
+            r#"
+types = ["feat", "fix"]
+scopes = ["ui"]
+footers = ["Signed-off-by"]
+"#,
+        )
+        .unwrap();
+        let (mut server, _) = open(&config, "f\n\nbody\n\nS\n");
+        let completion = |server: &mut Server, line: u64, character: u64| {
+            request(
+                server,
+                "textDocument/completion",
+                json!({
+                    "textDocument": { "uri": URI },
+                    "position": { "line": line, "character": character },
+                }),
+            )
+        };
+
+        assert_eq!(labels(&completion(&mut server, 0, 1)), vec!["feat", "fix"]);
+        assert_eq!(
+            labels(&completion(&mut server, 4, 1)),
+            vec!["BREAKING CHANGE", "Refs", "Signed-off-by"]
+        );
+
+        server
+            .documents
+            .insert(URI.to_string(), "fix(\n".to_string());
+        assert_eq!(labels(&completion(&mut server, 0, 4)), vec!["ui"]);
+    }
+
+    #[test]
+    fn lifecycle() {
+        let config = english();
+        let mut server = Server::new(&config);
+        let initialized = request(&mut server, "initialize", json!({ "capabilities": {} }));
+        assert_eq!(initialized["capabilities"]["textDocumentSync"], 1);
+        assert_eq!(initialized["capabilities"]["positionEncoding"], "utf-16");
+
+        let unknown =
+            server.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "workspace/symbol" }));
+        assert_eq!(unknown[0]["error"]["code"], METHOD_NOT_FOUND);
+
+        assert_eq!(request(&mut server, "shutdown", Value::Null), Value::Null);
+        assert!(server.shutdown);
+    }
+}
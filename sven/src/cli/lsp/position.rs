@@ -0,0 +1,108 @@
+use serde_json::{json, Value};
+use sven::{Bytes, Encoding, Position, PositionIndex};
+
+/// Encodings of the positions the server speaks, by their LSP names
+const ENCODINGS: [(&str, Encoding); 3] = [
+    ("utf-8", Encoding::Utf8),
+    ("utf-16", Encoding::Utf16),
+    ("utf-32", Encoding::Chars),
+];
+
+/// Encoding of the positions, the first of the client's ones the server
+/// speaks, UTF-16 otherwise as every client is expected to speak it
+pub fn negotiate(capabilities: &Value) -> (&'static str, Encoding) {
+    let offered = capabilities["general"]["positionEncodings"].as_array();
+    offered
+        .into_iter()
+        .flatten()
+        .find_map(|offered| ENCODINGS.into_iter().find(|(name, _)| offered == name))
+        .unwrap_or(ENCODINGS[1])
+}
+
+/// LSP positions of the bytes of the text
+pub struct Positions<'t> {
+    index: PositionIndex<'t>,
+    encoding: Encoding,
+}
+
+impl<'t> Positions<'t> {
+    pub fn new(text: &'t str, encoding: Encoding) -> Self {
+        Self {
+            index: PositionIndex::new(text),
+            encoding,
+        }
+    }
+
+    /// LSP position of the byte, the lines start 0 unlike the rows
+    pub fn position(&self, at: usize) -> Value {
+        let Position { row, col } = self.index.position(at, self.encoding);
+        json!({ "line": row - 1, "character": col })
+    }
+
+    pub fn range(&self, bytes: Bytes) -> Value {
+        json!({ "start": self.position(bytes.start()), "end": self.position(bytes.end()) })
+    }
+
+    /// Byte at the LSP position, the position past the end of the line is
+    /// the end of the line
+    pub fn offset(&self, position: &Value) -> usize {
+        let number = |key: &str| position[key].as_u64().unwrap_or_default() as usize;
+        let position = Position {
+            row: number("line") + 1,
+            col: number("character"),
+        };
+        self.index.offset(position, self.encoding)
+    }
+
+    /// Bytes of the row without its line ending, the row past the end of the
+    /// text is the end of the text
+    pub fn row(&self, row: usize) -> Bytes {
+        let at = |col| self.index.offset(Position { row, col }, self.encoding);
+        Bytes::new(at(0), at(usize::MAX))
+    }
+}
+
+#[cfg(test)]
+mod lsp_positions {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const TEXT: &str = "fix: да\n🚀 x\r\nя";
+
+    #[test]
+    fn positions() {
+        let positions = Positions::new(TEXT, Encoding::Utf16);
+        assert_eq!(positions.position(9), json!({ "line": 0, "character": 7 }));
+        // the emoji takes 4 bytes and 2 code units
+        assert_eq!(positions.position(15), json!({ "line": 1, "character": 3 }));
+        assert_eq!(positions.position(18), json!({ "line": 2, "character": 0 }));
+    }
+
+    #[test]
+    fn offsets() {
+        let positions = Positions::new(TEXT, Encoding::Utf16);
+        let at = |line: usize, character: usize| json!({ "line": line, "character": character });
+        assert_eq!(positions.offset(&at(0, 7)), 9);
+        assert_eq!(positions.offset(&at(1, 2)), 14);
+        assert_eq!(positions.offset(&at(1, 99)), 16);
+        assert_eq!(positions.offset(&at(9, 0)), TEXT.len());
+        assert_eq!(positions.row(2), Bytes::new(10, 16));
+    }
+
+    #[test]
+    fn negotiation() {
+        let offered = |encodings: Value| json!({ "general": { "positionEncodings": encodings } });
+        assert_eq!(negotiate(&json!({})), ("utf-16", Encoding::Utf16));
+        assert_eq!(
+            negotiate(&offered(json!(["utf-32", "utf-8"]))),
+            ("utf-32", Encoding::Chars)
+        );
+        assert_eq!(
+            negotiate(&offered(json!(["utf-7", "utf-8"]))),
+            ("utf-8", Encoding::Utf8)
+        );
+
+        let positions = Positions::new(TEXT, Encoding::Chars);
+        assert_eq!(positions.position(15), json!({ "line": 1, "character": 2 }));
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Read the message framed with the `Content-Length` header,
+/// nothing when the input is over
+pub fn read(input: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut len = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                len = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let Some(len) = len else {
+        bail!("missing Content-Length");
+    };
+    let mut body = vec![0; len];
+    input.read_exact(&mut body).context("reading the message")?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Write the message framed with the `Content-Length` header
+pub fn write(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod framing {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let message = json!({ "jsonrpc": "2.0", "method": "exit" });
+        let mut framed = Vec::new();
+        write(&mut framed, &message).unwrap();
+        write(&mut framed, &message).unwrap();
+
+        let mut input = framed.as_slice();
+        assert_eq!(read(&mut input).unwrap(), Some(message.clone()));
+        assert_eq!(read(&mut input).unwrap(), Some(message));
+        assert_eq!(read(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn content_type() {
+        let body = r#"{"id":1}"#;
+        let framed = format!(
+            "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let mut input = framed.as_bytes();
+        assert_eq!(read(&mut input).unwrap(), Some(json!({ "id": 1 })));
+    }
+}
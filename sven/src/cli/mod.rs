@@ -0,0 +1,19 @@
+mod branch;
+mod bump;
+mod changelog;
+mod check;
+mod commit;
+mod git;
+mod lsp;
+mod pre_receive;
+mod render;
+mod title;
+
+pub use branch::branch;
+pub use bump::bump;
+pub use changelog::changelog;
+pub use check::check;
+pub use commit::commit;
+pub use lsp::lsp;
+pub use pre_receive::pre_receive;
+pub use title::title;
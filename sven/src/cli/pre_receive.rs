@@ -0,0 +1,159 @@
+use super::{git, render};
+use anyhow::{bail, Result};
+use std::{
+    collections::HashSet,
+    io::{self, Read},
+    process::ExitCode,
+};
+use sven::{analyze, Arg, Config, Message};
+
+/// Analyse the commits pushed to the server, reading the ref updates from
+/// stdin the way git passes them to the `pre-receive` hook, and reject the
+/// push when any of the new commits is not a conventional one
+pub fn pre_receive(config: &Config) -> ExitCode {
+    let mut updates = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut updates) {
+        let e = e.into();
+        eprintln!(
+            "{}",
+            render::failure("pre-receive-read", &e, config.locale())
+        );
+        return ExitCode::FAILURE;
+    }
+
+    match rejected(config, &updates) {
+        Ok(rejected) if rejected.is_empty() => ExitCode::SUCCESS,
+        Ok(rejected) => {
+            for line in &rejected {
+                eprintln!("{}", line);
+            }
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                render::failure("pre-receive-analyze", &e, config.locale())
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Summary of the commits which are not conventional,
+/// nothing when every commit is. The commit new to several refs
+/// is analysed once, along with the first of them.
+fn rejected(config: &Config, updates: &str) -> Result<Vec<String>> {
+    let mut summary = Vec::new();
+    let mut count = 0;
+    let mut seen = HashSet::new();
+
+    for line in updates.lines().filter(|line| !line.trim().is_empty()) {
+        let update = Update::parse(line)?;
+        let Some(revisions) = update.revisions() else {
+            continue;
+        };
+
+        for (sha, message) in git::log(&revisions)? {
+            if !seen.insert(sha.clone()) {
+                continue;
+            }
+            let issues: Vec<String> = match analyze(&message, config) {
+                Ok(report) => render::issues(&report, config.locale())
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                Err(e) => vec![e.message().localize(config.locale())],
+            };
+            if issues.is_empty() {
+                continue;
+            }
+
+            count += 1;
+            let header = message.lines().next().unwrap_or_default();
+            summary.push(format!(
+                "{} {} {}",
+                &sha[..sha.len().min(7)],
+                update.name,
+                header
+            ));
+            summary.extend(issues.iter().map(|issue| format!("    {}", issue)));
+        }
+    }
+
+    if count > 0 {
+        let message = Message::new("pre-receive-rejected").arg("count", Arg::Number(count));
+        summary.push(message.localize(config.locale()));
+    }
+    Ok(summary)
+}
+
+/// Ref update, one of the lines git passes to the `pre-receive` hook
+#[derive(Debug, PartialEq, Eq)]
+struct Update<'l> {
+    new: &'l str,
+    name: &'l str,
+}
+
+impl<'l> Update<'l> {
+    /// `<old> <new> <ref>`
+    fn parse(line: &'l str) -> Result<Self> {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(_old), Some(new), Some(name), None) => Ok(Self { new, name }),
+            _ => bail!("expected `<old> <new> <ref>`, got `{}`", line),
+        }
+    }
+
+    /// Revisions of the new commits for `git log`, nothing when the ref is
+    /// deleted. The refs are not updated until the hook accepts the push, so
+    /// the commits not reachable from any ref yet are the new ones, both for
+    /// the created and the updated refs.
+    fn revisions(&self) -> Option<Vec<&'l str>> {
+        match is_zero(self.new) {
+            true => None,
+            false => Some(vec![self.new, "--not", "--all"]),
+        }
+    }
+}
+
+/// The SHA git passes for the ref which does not exist
+fn is_zero(sha: &str) -> bool {
+    sha.chars().all(|c| c == '0')
+}
+
+#[cfg(test)]
+mod update {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        let update =
+            Update::parse("0000000000000000000000000000000000000000 1a2b3c4d refs/heads/main")
+                .unwrap();
+        assert_eq!(update.name, "refs/heads/main");
+        assert!(Update::parse("1a2b3c4d refs/heads/main").is_err());
+    }
+
+    #[test]
+    fn revisions() {
+        let created =
+            Update::parse("0000000000000000000000000000000000000000 1a2b3c4d refs/heads/me")
+                .unwrap();
+        assert_eq!(
+            created.revisions(),
+            Some(vec!["1a2b3c4d", "--not", "--all"])
+        );
+
+        let deleted =
+            Update::parse("1a2b3c4d 0000000000000000000000000000000000000000 refs/heads/me")
+                .unwrap();
+        assert_eq!(deleted.revisions(), None);
+
+        let updated = Update::parse("1a2b3c4d 5e6f7a8b refs/heads/me").unwrap();
+        assert_eq!(
+            updated.revisions(),
+            Some(vec!["5e6f7a8b", "--not", "--all"])
+        );
+    }
+}
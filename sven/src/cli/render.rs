@@ -0,0 +1,186 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fmt::Display;
+use sven::{branch, Arg, Bytes, Error, Locale, Message, Report};
+
+/// How the issues are printed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One issue per line, to stderr
+    #[default]
+    Text,
+
+    /// Array of the issues, to stdout
+    Json,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => bail!("unknown format {}, expected text or json", format),
+        }
+    }
+}
+
+/// Issue along with where it is found, either the bytes
+/// of the input or the row of the commit
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row: Option<usize>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn at(bytes: Bytes, message: Message, locale: Locale) -> Self {
+        Self {
+            start: Some(bytes.start()),
+            end: Some(bytes.end()),
+            row: None,
+            message: message.localize(locale),
+        }
+    }
+
+    fn row(row: Option<usize>, message: Message, locale: Locale) -> Self {
+        Self {
+            start: None,
+            end: None,
+            row,
+            message: message.localize(locale),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.start, self.end, self.row) {
+            (Some(start), Some(end), _) => write!(f, "{}..{}: {}", start, end, self.message),
+            (_, _, Some(row)) => write!(f, "row {}: {}", row, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Every issue of the report, the way the hooks print them
+pub fn issues(report: &Report, locale: Locale) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for issue in &report.header {
+        diagnostics.push(Diagnostic::at(issue.bytes(), issue.message(), locale));
+    }
+    for issue in &report.shape {
+        diagnostics.push(Diagnostic::row(None, issue.message(), locale));
+    }
+
+    // footers go in the order of the rows
+    let mut rows: Vec<_> = report.footers.iter().collect();
+    rows.sort_by_key(|(row, _)| **row);
+    for (row, issues) in rows {
+        for issue in issues {
+            diagnostics.push(Diagnostic::row(Some(*row), issue.message(), locale));
+        }
+    }
+
+    diagnostics
+}
+
+/// Title which can not be analysed, as its only issue: the line ending
+/// found in it, or nothing at all to analyse
+pub fn title_error(error: &Error, locale: Locale) -> Diagnostic {
+    match error {
+        Error::Multiline(at) => Diagnostic::at(Bytes::new(*at, *at + 1), error.message(), locale),
+        _ => Diagnostic::row(None, error.message(), locale),
+    }
+}
+
+/// Every issue of the branch name
+pub fn branch_issues(issues: &[branch::Issue], locale: Locale) -> Vec<Diagnostic> {
+    issues
+        .iter()
+        .map(|issue| Diagnostic::at(issue.bytes(), issue.message(), locale))
+        .collect()
+}
+
+/// Why the command failed, the message of the id with the error in its
+/// `error` placeable, which is localised too when it comes from the analysis
+pub fn failure(id: &'static str, error: &anyhow::Error, locale: Locale) -> String {
+    let error = match error.downcast_ref::<Error>() {
+        Some(error) => error.message().localize(locale),
+        None => error.to_string(),
+    };
+    Message::new(id)
+        .arg("error", Arg::Text(error))
+        .localize(locale)
+}
+
+/// Print the issues in the format, the array is printed even when empty
+pub fn print(diagnostics: &[Diagnostic], format: Format) {
+    match format {
+        Format::Text => {
+            for diagnostic in diagnostics {
+                eprintln!("{}", diagnostic);
+            }
+        }
+        Format::Json => match serde_json::to_string(diagnostics) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("could not print the issues: {}", e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod diagnostics {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use sven::{analyze, Config};
+
+    #[test]
+    fn text_and_json() {
+        let config = Config::parse(r#"footers = ["Refs"]"#).unwrap();
+        let report = analyze("fix me\n", &config).unwrap();
+        let diagnostics = issues(&report, Locale::En);
+
+        let text: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            text,
+            vec![
+                "3..3: missing colon",
+                "row 2: missing required footer \"Refs\""
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&diagnostics).unwrap(),
+            r#"[{"start":3,"end":3,"message":"missing colon"},{"row":2,"message":"missing required footer \"Refs\""}]"#
+        );
+    }
+
+    #[test]
+    fn localized() {
+        let report = analyze("fix me\n", &Config::default()).unwrap();
+        let text: Vec<String> = issues(&report, Locale::Ru)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(text, vec!["3..3: отсутствует двоеточие"]);
+    }
+
+    #[test]
+    fn failures() {
+        let error = Error::InvalidUtf8(5).into();
+        assert_eq!(
+            failure("check-analyze", &error, Locale::Ru),
+            "не удалось проанализировать коммит: недопустимый UTF-8 в байте 5"
+        );
+        let error = anyhow::anyhow!("HEAD is not a commit");
+        assert_eq!(
+            failure("bump-failed", &error, Locale::En),
+            "could not bump the version: HEAD is not a commit"
+        );
+    }
+}
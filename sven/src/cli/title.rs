@@ -0,0 +1,58 @@
+use super::render::{self, Format};
+use anyhow::{bail, Result};
+use std::process::ExitCode;
+use sven::{analyze_title, Config, Error};
+
+/// Analyze the title of the pull request, the header of
+/// the commit it is squashed into
+pub fn title(config: &Config, args: &[String]) -> ExitCode {
+    match run(config, args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", render::failure("title-analyze", &e, config.locale()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(config: &Config, args: &[String]) -> Result<bool> {
+    let mut format = Format::Text;
+    let mut title = None;
+    // everything after `--` is the title, even when it looks like an option
+    let mut options = true;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" if options => options = false,
+            "--format" if options => match args.next() {
+                Some(value) => format = Format::parse(value)?,
+                None => bail!("expected the format after --format"),
+            },
+            arg if options && arg.starts_with("--format=") => {
+                format = Format::parse(&arg["--format=".len()..])?;
+            }
+            arg if options && arg.starts_with("--") => bail!("unknown option {}", arg),
+            arg => match title {
+                None => title = Some(arg),
+                Some(_) => bail!("expected a single title, quote it"),
+            },
+        }
+    }
+
+    let Some(title) = title else {
+        bail!("expected the title");
+    };
+
+    let report = match analyze_title(title, config) {
+        Ok(report) => report,
+        // the title which is not a single line is an issue of its own
+        Err(e @ (Error::Multiline(_) | Error::Empty)) => {
+            render::print(&[render::title_error(&e, config.locale())], format);
+            return Ok(false);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    render::print(&render::issues(&report, config.locale()), format);
+    Ok(report.is_ok())
+}
@@ -1,8 +1,29 @@
-use std::collections::HashSet;
+use crate::{
+    bump::Bump,
+    changelog::Section,
+    cleanup::{Cleanup, COMMENT},
+    ignore::ignored,
+    message::Locale,
+};
+use anyhow::Result;
+use regex::Regex;
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
 
-#[derive(Debug)]
+/// File the configuration is read from, relative to the repository root
+pub const CONFIG_FILE: &str = ".sven.toml";
+
+#[derive(Debug, Default)]
 pub enum TypeRule {
     /// Match first Seq from the input
+    #[default]
     AnyFirstSeq,
 
     /// Seq from the input is expected to _strictly_
@@ -10,11 +31,361 @@ pub enum TypeRule {
     Strict(HashSet<String>),
 
     /// Seq from the input is expected to _roughly_
-    /// match predefined set
+    /// match predefined set, not supported by the analysis yet,
+    /// see [`crate::Error::Unsupported`]
     Like(HashSet<String>),
 }
 
+impl TypeRule {
+    /// Types of the set in the alphabetical order, nothing when any type goes
+    pub fn sorted(&self) -> Option<Vec<&str>> {
+        match self {
+            TypeRule::AnyFirstSeq => None,
+            TypeRule::Strict(set) | TypeRule::Like(set) => Some(sorted(set)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub enum ScopeRule {
+    /// Any scope is fine, as well as no scope at all
+    #[default]
+    Any,
+
+    /// Scope, when present, is expected to _strictly_
+    /// match predefined set
+    Strict(HashSet<String>),
+}
+
+impl ScopeRule {
+    /// Scopes of the set in the alphabetical order, nothing when any scope goes
+    pub fn sorted(&self) -> Option<Vec<&str>> {
+        match self {
+            ScopeRule::Any => None,
+            ScopeRule::Strict(set) => Some(sorted(set)),
+        }
+    }
+}
+
+fn sorted(set: &HashSet<String>) -> Vec<&str> {
+    let mut all: Vec<&str> = set.iter().map(String::as_str).collect();
+    all.sort_unstable();
+    all
+}
+
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Config {
     pub type_rule: TypeRule,
+    pub scope_rule: ScopeRule,
+
+    /// Tokens of the footers every commit is expected to have
+    pub required_footers: Vec<String>,
+
+    /// How the commit message is cleaned up before the analysis
+    pub cleanup: Cleanup,
+
+    /// Lines starting with it are comments, see git's `core.commentChar`
+    pub comment_char: char,
+
+    /// What commits of the type bump, on top of `feat` and `fix`
+    pub bump: HashMap<String, Bump>,
+
+    /// Whether breaking changes bump the minor until `1.0.0`,
+    /// see [`Bump::within`]
+    pub initial_development: bool,
+
+    /// Changelog sections, in order
+    pub changelog: Vec<Section>,
+
+    /// Path globs along with the scopes of the paths matching them, in the
+    /// order of the file, the first glob the path matches gives its scope
+    pub scope_paths: Vec<(String, String)>,
+
+    /// Patterns of the headers of the commits which are not analysed at
+    /// all, [`crate::IGNORED`] by default
+    pub ignore: Vec<Regex>,
+
+    /// Globs of the branches exempt from the naming convention,
+    /// see [`crate::analyze_branch`]
+    pub exempt_branches: Vec<String>,
+
+    /// Language of the messages, the one of the environment when not set,
+    /// see [`Config::locale`]
+    pub locale: Option<Locale>,
+
+    /// Aliases of the types along with the types they stand for, e.g.
+    /// `✨` for `feat`, accepted wherever the type is
+    pub aliases: HashMap<String, String>,
+
+    /// Whether the fix rewrites the alias to the type it stands for,
+    /// see [`crate::canonicalize`]
+    pub rewrite_aliases: bool,
+}
+
+/// Branches exempt from the naming convention when there are none in the config
+fn exempt_branches() -> Vec<String> {
+    vec![
+        "main".to_string(),
+        "master".to_string(),
+        "develop".to_string(),
+    ]
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            type_rule: TypeRule::default(),
+            scope_rule: ScopeRule::default(),
+            required_footers: Vec::new(),
+            cleanup: Cleanup::default(),
+            comment_char: COMMENT,
+            bump: HashMap::new(),
+            initial_development: false,
+            changelog: Section::defaults(),
+            scope_paths: Vec::new(),
+            ignore: ignored(),
+            exempt_branches: exempt_branches(),
+            locale: None,
+            aliases: HashMap::new(),
+            rewrite_aliases: false,
+        }
+    }
+}
+
+/// How the config looks like in the file
+///
+/// ```toml
+/// types = ["feat", "fix"]
+/// scopes = ["app", "ui"]
+/// footers = ["Refs"]
+/// cleanup = "strip"
+/// comment_char = "#"
+/// bump = { perf = "patch" }
+/// initial_development = true
+/// changelog = [{ type = "feat", title = "Features" }]
+/// ignore = ["^WIP "]
+/// branches = ["main", "release/*"]
+/// locale = "ru"
+/// rewrite_aliases = true
+///
+/// [paths]
+/// "packages/app/**" = "app"
+///
+/// [aliases]
+/// "✨" = "feat"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    types: Option<Vec<String>>,
+    scopes: Option<Vec<String>>,
+    footers: Option<Vec<String>>,
+    cleanup: Option<Cleanup>,
+    comment_char: Option<char>,
+    bump: Option<HashMap<String, Bump>>,
+    initial_development: Option<bool>,
+    changelog: Option<Vec<Section>>,
+    #[serde(default, deserialize_with = "in_order")]
+    paths: Option<Vec<(String, String)>>,
+    ignore: Option<Vec<String>>,
+    branches: Option<Vec<String>>,
+    locale: Option<Locale>,
+    aliases: Option<HashMap<String, String>>,
+    rewrite_aliases: Option<bool>,
+}
+
+/// Entries of the table in the order of the file, the way the globs of the
+/// paths are tried
+fn in_order<'de, D>(deserializer: D) -> Result<Option<Vec<(String, String)>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct Entries;
+
+    impl<'de> Visitor<'de> for Entries {
+        type Value = Vec<(String, String)>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a table of strings")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(Entries).map(Some)
+}
+
+impl Config {
+    /// Parse the config from the toml source
+    ///
+    /// ```
+    /// use sven::{Config, TypeRule};
+    ///
+    /// let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+    /// assert!(matches!(config.type_rule, TypeRule::Strict(_)));
+    /// ```
+    pub fn parse(source: &str) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(source)?;
+
+        let mut ignore = ignored();
+        for pattern in file.ignore.unwrap_or_default() {
+            ignore.push(Regex::new(&pattern)?);
+        }
+
+        Ok(Self {
+            type_rule: match file.types {
+                Some(types) => TypeRule::Strict(types.into_iter().collect()),
+                None => TypeRule::AnyFirstSeq,
+            },
+            scope_rule: match file.scopes {
+                Some(scopes) => ScopeRule::Strict(scopes.into_iter().collect()),
+                None => ScopeRule::Any,
+            },
+            required_footers: file.footers.unwrap_or_default(),
+            cleanup: file.cleanup.unwrap_or_default(),
+            comment_char: file.comment_char.unwrap_or(COMMENT),
+            bump: file.bump.unwrap_or_default(),
+            initial_development: file.initial_development.unwrap_or_default(),
+            changelog: file.changelog.unwrap_or_else(Section::defaults),
+            scope_paths: file.paths.unwrap_or_default(),
+            ignore,
+            exempt_branches: file.branches.unwrap_or_else(exempt_branches),
+            locale: file.locale,
+            aliases: file.aliases.unwrap_or_default(),
+            rewrite_aliases: file.rewrite_aliases.unwrap_or_default(),
+        })
+    }
+
+    /// Language of the messages, the configured one or the one of the
+    /// environment, see [`Locale::from_env`]
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or_else(Locale::from_env)
+    }
+
+    /// Type the alias stands for, or the type itself when it is not an alias
+    ///
+    /// ```
+    /// use sven::Config;
+    ///
+    /// let config = Config::parse("[aliases]\nbugfix = \"fix\"").unwrap();
+    /// assert_eq!(config.canonical("bugfix"), "fix");
+    /// assert_eq!(config.canonical("feat"), "feat");
+    /// ```
+    pub fn canonical<'a>(&'a self, kind: &'a str) -> &'a str {
+        self.aliases.get(kind).map_or(kind, String::as_str)
+    }
+
+    /// Read the config from the file, falling back to the default
+    /// config when there is no such file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(source) => Self::parse(&source),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod parse {
+    use super::*;
+    use crate::ignore::IGNORED;
+
+    #[test]
+    fn empty() {
+        let config = Config::parse("").unwrap();
+        assert!(matches!(config.type_rule, TypeRule::AnyFirstSeq));
+        assert!(matches!(config.scope_rule, ScopeRule::Any));
+        assert!(config.required_footers.is_empty());
+    }
+
+    #[test]
+    fn full() {
+        let config = Config::parse(
+            r#"
+types = ["feat", "fix"]
+scopes = ["app"]
+footers = ["Refs"]
+"#,
+        )
+        .unwrap();
+        assert!(matches!(config.type_rule, TypeRule::Strict(set) if set.len() == 2));
+        assert!(matches!(config.scope_rule, ScopeRule::Strict(set) if set.contains("app")));
+        assert_eq!(config.required_footers, vec!["Refs".to_string()]);
+    }
+
+    #[test]
+    fn paths() {
+        let config = Config::parse(
+            r#"
+[paths]
+"packages/ui/**" = "ui"
+"packages/app/**" = "app"
+"packages/**" = "packages"
+"#,
+        )
+        .unwrap();
+        let globs: Vec<&str> = config
+            .scope_paths
+            .iter()
+            .map(|(glob, _)| glob.as_str())
+            .collect();
+        assert_eq!(globs, ["packages/ui/**", "packages/app/**", "packages/**"]);
+    }
+
+    #[test]
+    fn cleanup() {
+        let config = Config::parse(
+            r#"
+cleanup = "scissors"
+comment_char = ";"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.cleanup, Cleanup::Scissors);
+        assert_eq!(config.comment_char, ';');
+    }
+
+    #[test]
+    fn ignore() {
+        let config = Config::parse(r#"ignore = ["^WIP "]"#).unwrap();
+        assert_eq!(config.ignore.len(), IGNORED.len() + 1);
+        assert!(Config::parse(r#"ignore = ["("]"#).is_err());
+    }
+
+    #[test]
+    fn locale() {
+        let config = Config::parse(r#"locale = "ru""#).unwrap();
+        assert_eq!(config.locale(), Locale::Ru);
+        assert!(Config::parse(r#"locale = "xx""#).is_err());
+    }
+
+    #[test]
+    fn aliases() {
+        let config = Config::parse(
+            r#"
+rewrite_aliases = true
+
+[aliases]
+"исправление" = "fix"
+"✨" = "feat"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.canonical("исправление"), "fix");
+        assert_eq!(config.canonical("✨"), "feat");
+        assert!(config.rewrite_aliases);
+    }
+
+    #[test]
+    fn unknown_field() {
+        assert!(Config::parse("typos = []").is_err());
+    }
 }
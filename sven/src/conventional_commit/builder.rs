@@ -0,0 +1,221 @@
+use super::{
+    footer::is_token,
+    header::is_word,
+    owned::{OwnedCommitFooter, OwnedCommitHeader, OwnedConventionalCommit},
+    ParsedCommit,
+};
+use crate::error::Error;
+
+/// Builder of the [`OwnedConventionalCommit`] which is checked to be
+/// displayed as a valid conventional commit, and to be parsed back the same
+///
+/// ```
+/// use sven::OwnedConventionalCommit;
+///
+/// let commit = OwnedConventionalCommit::builder("feat", "log in with the passkey")
+///     .scope("ui")
+///     .body("Passwords are still accepted.")
+///     .footer("Refs", "#42")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     commit.to_string(),
+///     "feat(ui): log in with the passkey\n\nPasswords are still accepted.\n\nRefs: #42\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommitBuilder {
+    commit: OwnedConventionalCommit,
+}
+
+impl CommitBuilder {
+    pub fn new(kind: impl Into<String>, desc: impl Into<String>) -> Self {
+        Self {
+            commit: OwnedConventionalCommit {
+                header: OwnedCommitHeader {
+                    kind: kind.into(),
+                    scope: None,
+                    desc: desc.into(),
+                    breaking_change: false,
+                },
+                body: None,
+                footers: Vec::new(),
+            },
+        }
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.commit.header.scope = Some(scope.into());
+        self
+    }
+
+    /// `!` after the type and the scope
+    pub fn breaking(mut self) -> Self {
+        self.commit.header.breaking_change = true;
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.commit.body = Some(body.into());
+        self
+    }
+
+    /// `token: value` footer
+    pub fn footer(mut self, token: impl Into<String>, value: impl Into<String>) -> Self {
+        let footer = OwnedCommitFooter::Simple(token.into(), value.into());
+        self.commit.footers.push(footer);
+        self
+    }
+
+    /// `BREAKING CHANGE: value` footer
+    pub fn breaking_change(mut self, value: impl Into<String>) -> Self {
+        let footer = OwnedCommitFooter::BreakingChange(value.into());
+        self.commit.footers.push(footer);
+        self
+    }
+
+    /// `token #value` footer
+    pub fn trailer(mut self, token: impl Into<String>, value: impl Into<String>) -> Self {
+        let footer = OwnedCommitFooter::Trailer(token.into(), '#', value.into());
+        self.commit.footers.push(footer);
+        self
+    }
+
+    /// The commit, unless one of its fields is invalid: the type or the scope
+    /// is not a single word, the description or the body is blank or not
+    /// trimmed, or the footer token is not made of alphanumerics and `-`.
+    /// Footer values may span several lines as long as the lines following
+    /// the first one are indented.
+    pub fn build(self) -> Result<OwnedConventionalCommit, Error> {
+        let commit = self.commit;
+        let header = &commit.header;
+
+        if !is_words(&header.kind) {
+            return Err(Error::Invalid("type"));
+        }
+        if header
+            .scope
+            .as_deref()
+            .is_some_and(|scope| !is_words(scope))
+        {
+            return Err(Error::Invalid("scope"));
+        }
+        if !is_trimmed(&header.desc) || header.desc.contains(['\n', '\r']) {
+            return Err(Error::Invalid("description"));
+        }
+        if commit.body.as_deref().is_some_and(|body| !is_trimmed(body)) {
+            return Err(Error::Invalid("body"));
+        }
+
+        for footer in &commit.footers {
+            let (token, value) = match footer {
+                OwnedCommitFooter::Simple(token, value) => (token.as_str(), value),
+                OwnedCommitFooter::BreakingChange(value) => ("BREAKING CHANGE", value),
+                OwnedCommitFooter::Trailer(token, _, value) => (token.as_str(), value),
+            };
+            let breaking_change = matches!(footer, OwnedCommitFooter::BreakingChange(_));
+            if !breaking_change && (token == "BREAKING-CHANGE" || !token.chars().all(is_token)) {
+                return Err(Error::Invalid("footer token"));
+            }
+            if !is_trimmed(value) || !value.lines().skip(1).all(is_continuation) {
+                return Err(Error::Invalid("footer value"));
+            }
+        }
+
+        // the body ending with a paragraph of footers would be read as them
+        if ParsedCommit::parse(&commit.to_string()).as_ref() != Ok(&commit.as_parsed()) {
+            return Err(Error::Invalid("body"));
+        }
+
+        Ok(commit)
+    }
+}
+
+#[inline]
+fn is_words(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(is_word)
+}
+
+#[inline]
+fn is_trimmed(text: &str) -> bool {
+    !text.is_empty() && text.trim() == text
+}
+
+#[inline]
+fn is_continuation(line: &str) -> bool {
+    line.starts_with([' ', '\t']) && !line.trim().is_empty()
+}
+
+#[cfg(test)]
+mod build {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_field() {
+        let commit = CommitBuilder::new("feat", "me")
+            .scope("app")
+            .breaking()
+            .body("Один\n\nдва")
+            .footer("Refs", "#1")
+            .breaking_change("one\n  two")
+            .trailer("Closes", "2")
+            .build()
+            .unwrap();
+        let expected = "\
+feat(app)!: me
+
+Один
+
+два
+
+Refs: #1
+BREAKING CHANGE: one
+  two
+Closes #2
+";
+        assert_eq!(commit.to_string(), expected);
+        assert_eq!(expected.parse(), Ok(commit));
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            (CommitBuilder::new("", "me"), "type"),
+            (CommitBuilder::new("fix it", "me"), "type"),
+            (CommitBuilder::new("fix", "me").scope("a)"), "scope"),
+            (CommitBuilder::new("fix", " me"), "description"),
+            (CommitBuilder::new("fix", "me\nyou"), "description"),
+            (CommitBuilder::new("fix", "me").body(""), "body"),
+            (CommitBuilder::new("fix", "me").body("Refs: #1"), "body"),
+            (
+                CommitBuilder::new("fix", "me").footer("Signed off", "A"),
+                "footer token",
+            ),
+            (
+                CommitBuilder::new("fix", "me").footer("BREAKING-CHANGE", "A"),
+                "footer token",
+            ),
+            (
+                CommitBuilder::new("fix", "me").trailer("Closes", ""),
+                "footer value",
+            ),
+            (
+                CommitBuilder::new("fix", "me").breaking_change("one\ntwo"),
+                "footer value",
+            ),
+        ];
+        for (builder, field) in cases {
+            assert_eq!(builder.build(), Err(Error::Invalid(field)));
+        }
+    }
+
+    #[test]
+    fn footer_like_body_before_footers() {
+        let commit = CommitBuilder::new("fix", "me")
+            .body("Refs: #1")
+            .footer("Refs", "#2")
+            .build();
+        assert!(commit.is_ok());
+    }
+}
@@ -1,9 +1,106 @@
+use crate::error::Error;
 use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CommitFooter<'c> {
+    /// `token: value`
     Simple(&'c str, &'c str),
+
+    /// `BREAKING CHANGE: value`, or `BREAKING-CHANGE: value`
     BreakingChange(&'c str),
+
+    /// Trailer using a separator other than the colon, e.g. `token #value`
+    /// when `#` is one of the separators, or `token= value` for any other
+    /// separator, expected to be ASCII punctuation
+    Trailer(&'c str, char, &'c str),
+}
+
+impl<'c> CommitFooter<'c> {
+    pub fn token(&self) -> &'c str {
+        match self {
+            CommitFooter::Simple(k, _) => k,
+            CommitFooter::BreakingChange(_) => "BREAKING CHANGE",
+            CommitFooter::Trailer(k, _, _) => k,
+        }
+    }
+
+    pub fn value(&self) -> &'c str {
+        match self {
+            CommitFooter::Simple(_, v) => v,
+            CommitFooter::BreakingChange(v) => v,
+            CommitFooter::Trailer(_, _, v) => v,
+        }
+    }
+}
+
+/// Footers written exactly the way they are displayed, one per line with
+/// the continuation lines indented, every line ending with `\n`. The text
+/// is found at the given byte of the commit, the errors point to its bytes.
+pub(crate) fn parse(text: &str, at: usize) -> Result<Vec<CommitFooter<'_>>, Error> {
+    let mut footers = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let start = at + text.len() - rest.len();
+        let len = value_len(rest);
+        let line = rest[..len]
+            .strip_suffix('\n')
+            .ok_or(Error::Syntax(at + text.len(), "`\\n`"))?;
+        footers.push(footer(line, start)?);
+        rest = &rest[len..];
+    }
+
+    Ok(footers)
+}
+
+/// Length of the first line along with its continuation lines
+fn value_len(text: &str) -> usize {
+    let mut len = 0;
+    for line in text.split_inclusive('\n') {
+        if len > 0 && !line.starts_with([' ', '\t']) {
+            break;
+        }
+        len += line.len();
+    }
+    len
+}
+
+/// Char of the token other than `BREAKING CHANGE`
+#[inline]
+pub(crate) fn is_token(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-'
+}
+
+fn footer(line: &str, at: usize) -> Result<CommitFooter<'_>, Error> {
+    if let Some(value) = line.strip_prefix("BREAKING CHANGE: ") {
+        return Ok(CommitFooter::BreakingChange(value));
+    }
+
+    let len = line.find(|c| !is_token(c)).unwrap_or(line.len());
+    let (token, rest) = line.split_at(len);
+    if token.is_empty() {
+        return Err(Error::Syntax(at, "token"));
+    }
+
+    if let Some(value) = rest.strip_prefix(": ") {
+        return match token {
+            // it is displayed as `BREAKING CHANGE`
+            "BREAKING-CHANGE" => Err(Error::Syntax(at, "`BREAKING CHANGE`")),
+            _ => Ok(CommitFooter::Simple(token, value)),
+        };
+    }
+    if let Some(value) = rest.strip_prefix(" #") {
+        return Ok(CommitFooter::Trailer(token, '#', value));
+    }
+
+    // any other separator is displayed followed by the space
+    let mut chars = rest.chars();
+    match (chars.next(), chars.as_str().strip_prefix(' ')) {
+        (Some(sep), Some(value)) if sep.is_ascii_punctuation() => {
+            Ok(CommitFooter::Trailer(token, sep, value))
+        }
+        _ => Err(Error::Syntax(at + len, "`: `, ` #` or `<separator> `")),
+    }
 }
 
 impl Display for CommitFooter<'_> {
@@ -15,11 +112,18 @@ impl Display for CommitFooter<'_> {
             CommitFooter::BreakingChange(v) => {
                 write!(f, "BREAKING CHANGE: {}", v)
             }
+            CommitFooter::Trailer(k, '#', v) => {
+                write!(f, "{} #{}", k, v)
+            }
+            CommitFooter::Trailer(k, sep, v) => {
+                write!(f, "{}{} {}", k, sep, v)
+            }
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod footer {
     use super::*;
     use pretty_assertions::assert_eq;
@@ -31,6 +135,20 @@ mod footer {
         assert_eq!(format!("{}", actual), expected);
     }
 
+    #[test]
+    fn display_trailer_hash() {
+        let actual = CommitFooter::Trailer("Closes", '#', "42");
+        let expected = "Closes #42";
+        assert_eq!(format!("{}", actual), expected);
+    }
+
+    #[test]
+    fn display_trailer() {
+        let actual = CommitFooter::Trailer("Bug", '=', "42");
+        let expected = "Bug= 42";
+        assert_eq!(format!("{}", actual), expected);
+    }
+
     #[test]
     fn display_breaking_change() {
         let actual = CommitFooter::BreakingChange("Uses different version now");
@@ -38,3 +156,50 @@ mod footer {
         assert_eq!(format!("{}", actual), expected);
     }
 }
+
+#[cfg(test)]
+mod strict {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_kind() {
+        let text = "Refs: #1\nBREAKING CHANGE: one\n  two\nCloses #42\nBug= 42\n";
+        assert_eq!(
+            parse(text, 0).unwrap(),
+            vec![
+                CommitFooter::Simple("Refs", "#1"),
+                CommitFooter::BreakingChange("one\n  two"),
+                CommitFooter::Trailer("Closes", '#', "42"),
+                CommitFooter::Trailer("Bug", '=', "42"),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_displayed() {
+        let cases = [
+            (
+                "Refs:#1\n",
+                Error::Syntax(14, "`: `, ` #` or `<separator> `"),
+            ),
+            (
+                "Acked-by : X\n",
+                Error::Syntax(18, "`: `, ` #` or `<separator> `"),
+            ),
+            (
+                "Bug=42\n",
+                Error::Syntax(13, "`: `, ` #` or `<separator> `"),
+            ),
+            (
+                "BREAKING-CHANGE: one\n",
+                Error::Syntax(10, "`BREAKING CHANGE`"),
+            ),
+            (" two\n", Error::Syntax(10, "token")),
+            ("Refs: #1", Error::Syntax(18, "`\\n`")),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse(text, 10), Err(expected), "{:?}", text);
+        }
+    }
+}
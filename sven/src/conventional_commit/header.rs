@@ -1,6 +1,7 @@
+use crate::{config::Config, error::Error};
 use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct CommitHeader<'c> {
     pub kind: &'c str,
     pub scope: Option<&'c str>,
@@ -8,6 +9,102 @@ pub struct CommitHeader<'c> {
     pub breaking_change: bool,
 }
 
+impl<'c> CommitHeader<'c> {
+    /// Header written exactly the way it is displayed, without recovering
+    /// from any issue, see [`crate::analyze`] for that
+    ///
+    /// ```
+    /// use sven::{CommitHeader, Error};
+    ///
+    /// let header = CommitHeader::parse("feat(app)!: me").unwrap();
+    /// assert_eq!((header.kind, header.scope, header.breaking_change), ("feat", Some("app"), true));
+    ///
+    /// assert_eq!(CommitHeader::parse("feat:me"), Err(Error::Syntax(4, "`: `")));
+    /// ```
+    pub fn parse(header: &'c str) -> Result<Self, Error> {
+        parse(header, 0)
+    }
+
+    /// Same header with the alias of the type replaced with the type it
+    /// stands for, see [`Config::aliases`]
+    ///
+    /// ```
+    /// use sven::{analyze, Config};
+    ///
+    /// let config = Config::parse("[aliases]\n\"✨\" = \"feat\"").unwrap();
+    /// let commit = "✨: me";
+    /// let report = analyze(commit, &config).unwrap();
+    /// let header = report.commit_header(commit).unwrap().canonical(&config);
+    /// assert_eq!(header.kind, "feat");
+    /// ```
+    pub fn canonical(self, config: &'c Config) -> Self {
+        Self {
+            kind: config.canonical(self.kind),
+            ..self
+        }
+    }
+}
+
+/// Char of the type or the scope
+#[inline]
+pub(crate) fn is_word(c: char) -> bool {
+    !c.is_whitespace() && !"():!".contains(c)
+}
+
+impl<'c> TryFrom<&'c str> for CommitHeader<'c> {
+    type Error = Error;
+
+    fn try_from(header: &'c str) -> Result<Self, Self::Error> {
+        Self::parse(header)
+    }
+}
+
+/// Same as [`CommitHeader::parse`] for the header found at the given byte
+/// of the commit, the errors point to the bytes of the commit
+pub(crate) fn parse(header: &str, at: usize) -> Result<CommitHeader<'_>, Error> {
+    let kind_len = header.find(|c| !is_word(c)).unwrap_or(header.len());
+    let kind = &header[..kind_len];
+    if kind.is_empty() {
+        return Err(Error::Syntax(at, "type"));
+    }
+    let mut rest = &header[kind_len..];
+    let offset = |rest: &str| at + header.len() - rest.len();
+
+    let mut scope = None;
+    if let Some(after) = rest.strip_prefix('(') {
+        let len = after.find(|c| !is_word(c)).unwrap_or(after.len());
+        if len == 0 {
+            return Err(Error::Syntax(offset(after), "scope"));
+        }
+        scope = Some(&after[..len]);
+        rest = after[len..]
+            .strip_prefix(')')
+            .ok_or(Error::Syntax(offset(&after[len..]), "`)`"))?;
+    }
+
+    let breaking_change = match rest.strip_prefix('!') {
+        Some(after) => {
+            rest = after;
+            true
+        }
+        None => false,
+    };
+
+    let desc = rest
+        .strip_prefix(": ")
+        .ok_or(Error::Syntax(offset(rest), "`: `"))?;
+    if desc.is_empty() || desc.trim() != desc || desc.contains('\n') {
+        return Err(Error::Syntax(offset(desc), "description"));
+    }
+
+    Ok(CommitHeader {
+        kind,
+        scope,
+        desc,
+        breaking_change,
+    })
+}
+
 impl Display for CommitHeader<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.kind)?;
@@ -25,6 +122,7 @@ impl Display for CommitHeader<'_> {
 }
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod header {
     use super::*;
     use pretty_assertions::assert_eq;
@@ -77,3 +175,34 @@ mod header {
         assert_eq!(format!("{}", actual), expected);
     }
 }
+
+#[cfg(test)]
+mod strict {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn displayed() {
+        for header in ["fix: me", "fix(app): me", "fix!: me", "feat(app)!: да нет"] {
+            assert_eq!(CommitHeader::parse(header).unwrap().to_string(), header);
+        }
+    }
+
+    #[test]
+    fn not_displayed() {
+        let cases = [
+            ("", Error::Syntax(0, "type")),
+            (": me", Error::Syntax(0, "type")),
+            ("fix me", Error::Syntax(3, "`: `")),
+            ("fix(): me", Error::Syntax(4, "scope")),
+            ("fix(app: me", Error::Syntax(7, "`)`")),
+            ("fix!!: me", Error::Syntax(4, "`: `")),
+            ("fix:  me", Error::Syntax(5, "description")),
+            ("fix: me ", Error::Syntax(5, "description")),
+            ("fix: ", Error::Syntax(5, "description")),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(CommitHeader::parse(header), Err(expected), "{:?}", header);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::report::Report;
 use std::fmt::Display;
 
 mod header;
@@ -6,6 +7,22 @@ pub use header::CommitHeader;
 mod footer;
 pub use footer::CommitFooter;
 
+mod trailers;
+pub use trailers::{Trailers, SEPARATORS};
+
+mod parsed;
+pub use parsed::ParsedCommit;
+
+mod owned;
+pub use owned::{OwnedCommitFooter, OwnedCommitHeader, OwnedConventionalCommit};
+
+mod builder;
+pub use builder::CommitBuilder;
+
+mod revert;
+pub(crate) use revert::git_reverted;
+pub use revert::{cancel_reverts, Revert};
+
 /// [Conventional Commits Specification](https://www.conventionalcommits.org/en/v1.0.0/)
 ///
 /// Once this struct has been obtained, it might be safely assumed to have a
@@ -23,7 +40,24 @@ pub use footer::CommitFooter;
 /// Every text (a str really) contained in this struct is expected to be trimmed
 /// down because when `Display`'ing this struct it will format data in the
 /// expected for the specification way.
-#[derive(Debug)]
+///
+/// ```
+/// use sven::{CommitFooter, CommitHeader, ConventionalCommit};
+///
+/// let commit = ConventionalCommit {
+///     header: CommitHeader {
+///         kind: "fix",
+///         scope: Some("app"),
+///         desc: "a simple fix",
+///         breaking_change: false,
+///     },
+///     body: None,
+///     footers: &[CommitFooter::Simple("Refs", "#1001")],
+/// };
+///
+/// assert_eq!(commit.to_string(), "fix(app): a simple fix\n\nRefs: #1001\n");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ConventionalCommit<'c> {
     pub header: CommitHeader<'c>,
     /// If there is any body (get it?) it should start with the first utf8
@@ -33,18 +67,56 @@ pub struct ConventionalCommit<'c> {
     pub footers: &'c [CommitFooter<'c>],
 }
 
+impl<'c> ConventionalCommit<'c> {
+    /// Put the commit together from the analysis report of the message, as
+    /// long as its header has no issues. The message is expected to be cleaned
+    /// up already, the way git stores it.
+    ///
+    /// ```
+    /// use sven::{analyze, Config, ConventionalCommit, Trailers, COMMENT, SEPARATORS};
+    ///
+    /// let message = "fix: me\n\nBody\n\nRefs: #1\n";
+    /// let report = analyze(message, &Config::default()).unwrap();
+    /// let trailers = Trailers::parse(message, SEPARATORS, COMMENT);
+    ///
+    /// let commit = ConventionalCommit::from_report(message, &report, trailers.as_ref()).unwrap();
+    /// assert_eq!(commit.body, Some("Body"));
+    /// assert_eq!(commit.to_string(), message);
+    /// ```
+    pub fn from_report(
+        message: &'c str,
+        report: &Report,
+        trailers: Option<&'c Trailers<'c>>,
+    ) -> Option<Self> {
+        let header = report.commit_header(message)?;
+
+        let body_start = message.find('\n').map_or(message.len(), |at| at + 1);
+        let body_end = trailers.map_or(message.len(), |t| t.bytes.start());
+        let body = message
+            .get(body_start..body_end.max(body_start))
+            .map(|body| body.trim_matches(|c: char| c.is_ascii_whitespace()))
+            .filter(|body| !body.is_empty());
+
+        Some(Self {
+            header,
+            body,
+            footers: trailers.map_or(&[], |t| t.footers.as_slice()),
+        })
+    }
+}
+
 impl Display for ConventionalCommit<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}\n", self.header)?;
+        writeln!(f, "{}", self.header)?;
 
         if let Some(body) = self.body {
             write!(f, "\n{}\n", body)?;
         }
 
         if !self.footers.is_empty() {
-            write!(f, "\n")?;
+            writeln!(f)?;
             for footer in self.footers {
-                write!(f, "{}\n", footer)?;
+                writeln!(f, "{}", footer)?;
             }
         }
 
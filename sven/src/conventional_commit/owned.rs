@@ -0,0 +1,167 @@
+use super::{builder::CommitBuilder, CommitFooter, CommitHeader, ConventionalCommit, ParsedCommit};
+use crate::error::Error;
+use std::{fmt::Display, str::FromStr};
+
+/// Owned counterpart of [`CommitHeader`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OwnedCommitHeader {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub desc: String,
+    pub breaking_change: bool,
+}
+
+impl OwnedCommitHeader {
+    pub fn as_header(&self) -> CommitHeader<'_> {
+        CommitHeader {
+            kind: &self.kind,
+            scope: self.scope.as_deref(),
+            desc: &self.desc,
+            breaking_change: self.breaking_change,
+        }
+    }
+}
+
+impl From<CommitHeader<'_>> for OwnedCommitHeader {
+    fn from(header: CommitHeader<'_>) -> Self {
+        Self {
+            kind: header.kind.to_string(),
+            scope: header.scope.map(str::to_string),
+            desc: header.desc.to_string(),
+            breaking_change: header.breaking_change,
+        }
+    }
+}
+
+impl Display for OwnedCommitHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_header().fmt(f)
+    }
+}
+
+/// Owned counterpart of [`CommitFooter`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OwnedCommitFooter {
+    Simple(String, String),
+    BreakingChange(String),
+    Trailer(String, char, String),
+}
+
+impl OwnedCommitFooter {
+    pub fn as_footer(&self) -> CommitFooter<'_> {
+        match self {
+            Self::Simple(k, v) => CommitFooter::Simple(k, v),
+            Self::BreakingChange(v) => CommitFooter::BreakingChange(v),
+            Self::Trailer(k, sep, v) => CommitFooter::Trailer(k, *sep, v),
+        }
+    }
+}
+
+impl From<CommitFooter<'_>> for OwnedCommitFooter {
+    fn from(footer: CommitFooter<'_>) -> Self {
+        match footer {
+            CommitFooter::Simple(k, v) => Self::Simple(k.to_string(), v.to_string()),
+            CommitFooter::BreakingChange(v) => Self::BreakingChange(v.to_string()),
+            CommitFooter::Trailer(k, sep, v) => Self::Trailer(k.to_string(), sep, v.to_string()),
+        }
+    }
+}
+
+impl Display for OwnedCommitFooter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_footer().fmt(f)
+    }
+}
+
+/// Owned counterpart of [`ConventionalCommit`], to be stored or put together
+/// programmatically, see [`OwnedConventionalCommit::builder`]
+///
+/// ```
+/// use sven::OwnedConventionalCommit;
+///
+/// let commit: OwnedConventionalCommit = "fix(app): me\n\nRefs: #1\n".parse().unwrap();
+/// assert_eq!(commit.header.scope.as_deref(), Some("app"));
+/// assert_eq!(commit.to_string(), "fix(app): me\n\nRefs: #1\n");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OwnedConventionalCommit {
+    pub header: OwnedCommitHeader,
+    pub body: Option<String>,
+    pub footers: Vec<OwnedCommitFooter>,
+}
+
+impl OwnedConventionalCommit {
+    /// Builder of the commit with the given type and description
+    pub fn builder(kind: impl Into<String>, desc: impl Into<String>) -> CommitBuilder {
+        CommitBuilder::new(kind, desc)
+    }
+
+    /// Borrowed commit, whose [`ParsedCommit::as_commit`] is e.g. for
+    /// [`crate::Bump::of`] or [`crate::changelog`]
+    pub fn as_parsed(&self) -> ParsedCommit<'_> {
+        ParsedCommit {
+            header: self.header.as_header(),
+            body: self.body.as_deref(),
+            footers: self
+                .footers
+                .iter()
+                .map(OwnedCommitFooter::as_footer)
+                .collect(),
+        }
+    }
+}
+
+impl From<ConventionalCommit<'_>> for OwnedConventionalCommit {
+    fn from(commit: ConventionalCommit<'_>) -> Self {
+        Self {
+            header: commit.header.into(),
+            body: commit.body.map(str::to_string),
+            footers: commit.footers.iter().copied().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ParsedCommit<'_>> for OwnedConventionalCommit {
+    fn from(commit: ParsedCommit<'_>) -> Self {
+        commit.as_commit().into()
+    }
+}
+
+/// Same as [`ParsedCommit::parse`]
+impl FromStr for OwnedConventionalCommit {
+    type Err = Error;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        ParsedCommit::parse(message).map(Into::into)
+    }
+}
+
+impl Display for OwnedConventionalCommit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_parsed().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod conversions {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn borrowed_and_back() {
+        let message = "feat(app)!: me\n\nBody\n\nRefs: #1\nBREAKING CHANGE: gone\nCloses #2\n";
+        let parsed = ParsedCommit::parse(message).unwrap();
+        let owned = OwnedConventionalCommit::from(parsed.as_commit());
+        assert_eq!(owned.as_parsed(), parsed);
+        assert_eq!(OwnedConventionalCommit::from(parsed), owned);
+        assert_eq!(owned.to_string(), message);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "fix me\n".parse::<OwnedConventionalCommit>(),
+            Err(Error::Syntax(3, "`: `"))
+        );
+    }
+}
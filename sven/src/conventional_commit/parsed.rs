@@ -0,0 +1,152 @@
+use super::{footer, header, CommitFooter, CommitHeader, ConventionalCommit};
+use crate::error::Error;
+use std::fmt::Display;
+
+/// Commit read back from the message, holding the footers which
+/// [`ConventionalCommit`] borrows, see [`ParsedCommit::as_commit`]
+///
+/// ```
+/// use sven::{Bump, Config, ParsedCommit};
+///
+/// let commit = ParsedCommit::parse("feat: me\n\nBREAKING CHANGE: gone\n").unwrap();
+/// assert_eq!(Bump::of(&commit.as_commit(), &Config::default()), Bump::Major);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParsedCommit<'c> {
+    pub header: CommitHeader<'c>,
+    pub body: Option<&'c str>,
+    pub footers: Vec<CommitFooter<'c>>,
+}
+
+impl<'c> ParsedCommit<'c> {
+    /// Commit written exactly the way it is displayed, the inverse of
+    /// `Display`. Nothing is recovered from, the message is expected to be
+    /// produced by a tool rather than typed in, see [`crate::analyze`] for that.
+    ///
+    /// The last paragraph is read as the footers whenever it can be, so a body
+    /// ending with a paragraph of footers does not survive the round trip.
+    ///
+    /// ```
+    /// use sven::{CommitFooter, ParsedCommit};
+    ///
+    /// let message = "fix(app): me\n\nBody\n\nRefs: #1\nCloses #2\n";
+    /// let commit = ParsedCommit::parse(message).unwrap();
+    /// assert_eq!(commit.body, Some("Body"));
+    /// assert_eq!(commit.footers[1], CommitFooter::Trailer("Closes", '#', "2"));
+    /// assert_eq!(commit.to_string(), message);
+    /// ```
+    pub fn parse(message: &'c str) -> Result<Self, Error> {
+        let (header, rest) = message
+            .split_once('\n')
+            .ok_or(Error::Syntax(message.len(), "`\\n`"))?;
+        let header = header::parse(header, 0)?;
+        let at = message.len() - rest.len();
+
+        if rest.is_empty() {
+            return Ok(Self {
+                header,
+                body: None,
+                footers: Vec::new(),
+            });
+        }
+
+        let text = rest
+            .strip_prefix('\n')
+            .ok_or(Error::Syntax(at, "blank line"))?;
+        let at = at + 1;
+        if !text.ends_with('\n') {
+            return Err(Error::Syntax(message.len(), "`\\n`"));
+        }
+
+        if let Ok(footers) = footer::parse(text, at) {
+            return Ok(Self {
+                header,
+                body: None,
+                footers,
+            });
+        }
+
+        let (body, footers) = match text.rfind("\n\n") {
+            Some(end) => match footer::parse(&text[end + 2..], at + end + 2) {
+                Ok(footers) if !footers.is_empty() => (&text[..end], footers),
+                _ => (&text[..text.len() - 1], Vec::new()),
+            },
+            None => (&text[..text.len() - 1], Vec::new()),
+        };
+        if body.is_empty() {
+            return Err(Error::Syntax(at, "body"));
+        }
+
+        Ok(Self {
+            header,
+            body: Some(body),
+            footers,
+        })
+    }
+
+    /// Commit borrowing the footers, e.g. for [`crate::Bump::of`] or [`crate::changelog`]
+    pub fn as_commit(&self) -> ConventionalCommit<'_> {
+        ConventionalCommit {
+            header: self.header,
+            body: self.body,
+            footers: &self.footers,
+        }
+    }
+}
+
+impl<'c> TryFrom<&'c str> for ParsedCommit<'c> {
+    type Error = Error;
+
+    fn try_from(message: &'c str) -> Result<Self, Self::Error> {
+        Self::parse(message)
+    }
+}
+
+impl Display for ParsedCommit<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_commit().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod strict {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn round_trip(message: &str) {
+        let commit = ParsedCommit::parse(message).unwrap();
+        assert_eq!(commit.to_string(), message);
+        assert_eq!(ParsedCommit::try_from(message).unwrap(), commit);
+    }
+
+    #[test]
+    fn displayed() {
+        round_trip("fix: me\n");
+        round_trip("fix(app)!: me\n\nBody\n");
+        round_trip("fix: me\n\nRefs: #1\n");
+        round_trip(
+            "fix: me\n\nОдин\n\n\nдва\n\nRefs: #1\nBREAKING CHANGE: one\n  two\nCloses #2\n",
+        );
+    }
+
+    #[test]
+    fn body_with_colons() {
+        let commit = ParsedCommit::parse("fix: me\n\nsee: this\nand that\n").unwrap();
+        assert_eq!(commit.body, Some("see: this\nand that"));
+        assert_eq!(commit.footers, vec![]);
+    }
+
+    #[test]
+    fn not_displayed() {
+        let cases = [
+            ("fix: me", Error::Syntax(7, "`\\n`")),
+            ("fix: me\nBody\n", Error::Syntax(8, "blank line")),
+            ("fix: me\n\nBody", Error::Syntax(13, "`\\n`")),
+            ("fix: me\n\n\n", Error::Syntax(9, "body")),
+            ("fix:me\n", Error::Syntax(3, "`: `")),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(ParsedCommit::parse(message), Err(expected), "{:?}", message);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+use super::ParsedCommit;
+use super::{CommitFooter, CommitHeader, ConventionalCommit};
+
+/// What the revert commit reverts, either `revert: <header>` with the SHAs
+/// in the `Refs` footers, as the specification recommends, or the commit
+/// git writes itself:
+///
+/// ```text
+/// Revert "feat: me"
+///
+/// This reverts commit 1a2b3c4d.
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Revert<'c> {
+    /// Header of the reverted commit, when it is a conventional one
+    pub header: Option<CommitHeader<'c>>,
+
+    /// SHAs of the reverted commits, full or abbreviated
+    pub shas: Vec<&'c str>,
+}
+
+impl<'c> ConventionalCommit<'c> {
+    /// What the commit reverts, if it is a revert
+    ///
+    /// ```
+    /// use sven::{analyze, Config, ConventionalCommit, Trailers, COMMENT, SEPARATORS};
+    ///
+    /// let message = "Revert \"feat(app): me\"\n\nThis reverts commit 1a2b3c4d.\n";
+    /// let report = analyze(message, &Config::default()).unwrap();
+    /// let trailers = Trailers::parse(message, SEPARATORS, COMMENT);
+    /// let commit = ConventionalCommit::from_report(message, &report, trailers.as_ref()).unwrap();
+    ///
+    /// let revert = commit.revert().unwrap();
+    /// assert_eq!(revert.header.unwrap().scope, Some("app"));
+    /// assert_eq!(revert.shas, vec!["1a2b3c4d"]);
+    /// ```
+    pub fn revert(&self) -> Option<Revert<'c>> {
+        if !self.header.kind.eq_ignore_ascii_case("revert") {
+            return None;
+        }
+
+        let refs = self.footers.iter().filter_map(|footer| match footer {
+            CommitFooter::Simple(token, value) if token.eq_ignore_ascii_case("Refs") => {
+                Some(*value)
+            }
+            _ => None,
+        });
+        let reverts = self.body.into_iter().flat_map(|body| {
+            body.lines()
+                .filter_map(|line| line.trim().strip_prefix("This reverts commit "))
+                .map(|line| line.trim_end_matches('.'))
+        });
+        let shas = refs
+            .chain(reverts)
+            .flat_map(|value| value.split([',', ' ']))
+            .filter(|sha| is_sha(sha))
+            .collect();
+
+        Some(Revert {
+            header: CommitHeader::parse(self.header.desc).ok(),
+            shas,
+        })
+    }
+}
+
+/// Header of the reverted commit, in quotes within the header git writes
+/// when reverting
+pub(crate) fn git_reverted(header: &str) -> Option<&str> {
+    header
+        .strip_prefix("Revert \"")?
+        .strip_suffix('"')
+        .filter(|reverted| !reverted.is_empty())
+}
+
+/// Full or abbreviated SHA, git abbreviates them to 7 chars at least
+fn is_sha(text: &str) -> bool {
+    (7..=64).contains(&text.len()) && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Commits left once the reverts cancel out the commits they revert, both
+/// are left out. The commits are expected newest first, the way `git log`
+/// lists them, along with their SHAs. Reverts without the SHAs cancel out
+/// the commits with the reverted header.
+///
+/// ```
+/// use sven::{cancel_reverts, ParsedCommit};
+///
+/// let feat = ParsedCommit::parse("feat: me\n").unwrap();
+/// let fix = ParsedCommit::parse("fix: you\n").unwrap();
+/// let revert = ParsedCommit::parse("revert: feat: me\n\nRefs: 1a2b3c4d\n").unwrap();
+///
+/// let commits = [
+///     ("5e6f7a8b", revert.as_commit()),
+///     ("1a2b3c4d5e6f", feat.as_commit()),
+///     ("9c0d1e2f", fix.as_commit()),
+/// ];
+/// assert_eq!(cancel_reverts(&commits), vec![&commits[2].1]);
+/// ```
+pub fn cancel_reverts<'a, 'c>(
+    commits: &'a [(&str, ConventionalCommit<'c>)],
+) -> Vec<&'a ConventionalCommit<'c>> {
+    let mut cancelled = vec![false; commits.len()];
+
+    for (i, (_, commit)) in commits.iter().enumerate() {
+        // the revert which is reverted itself reverts nothing
+        if cancelled[i] {
+            continue;
+        }
+        let Some(revert) = commit.revert() else {
+            continue;
+        };
+
+        let mut reverted: Vec<usize> = Vec::new();
+        for (j, (sha, commit)) in commits.iter().enumerate().skip(i + 1) {
+            let is_reverted = match revert.shas.is_empty() {
+                true => reverted.is_empty() && revert.header == Some(commit.header),
+                false => revert.shas.iter().any(|r| sha.starts_with(r)),
+            };
+            if is_reverted && !cancelled[j] {
+                reverted.push(j);
+            }
+        }
+
+        let found = match revert.shas.is_empty() {
+            true => !reverted.is_empty(),
+            false => reverted.len() == revert.shas.len(),
+        };
+        cancelled[i] = found;
+        for j in reverted {
+            cancelled[j] = true;
+        }
+    }
+
+    commits
+        .iter()
+        .zip(cancelled)
+        .filter(|(_, cancelled)| !cancelled)
+        .map(|((_, commit), _)| commit)
+        .collect()
+}
+
+#[cfg(test)]
+mod reverts {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(message: &str) -> ParsedCommit<'_> {
+        ParsedCommit::parse(message).unwrap()
+    }
+
+    #[test]
+    fn refs() {
+        let commit = parse("revert: feat: me\n\nRefs: 1a2b3c4d, 5e6f7a8b\n");
+        let revert = commit.as_commit().revert().unwrap();
+        assert_eq!(revert.header, Some(parse("feat: me\n").header));
+        assert_eq!(revert.shas, vec!["1a2b3c4d", "5e6f7a8b"]);
+    }
+
+    #[test]
+    fn not_a_revert() {
+        assert_eq!(
+            parse("fix: me\n\nRefs: 1a2b3c4d\n").as_commit().revert(),
+            None
+        );
+    }
+
+    #[test]
+    fn not_conventional() {
+        let commit = parse("revert: let us never speak of it\n\nRefs: #1\n");
+        let revert = commit.as_commit().revert().unwrap();
+        assert_eq!(
+            revert,
+            Revert {
+                header: None,
+                shas: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn git_reverted_header() {
+        assert_eq!(git_reverted("Revert \"feat: me\""), Some("feat: me"));
+        assert_eq!(git_reverted("Revert \"\""), None);
+        assert_eq!(git_reverted("Revert feat: me"), None);
+    }
+
+    #[test]
+    fn cancelled() {
+        let parsed = [
+            ("aaaaaaa1", parse("revert: fix: two\n\nRefs: bbbbbbb\n")),
+            ("bbbbbbb2", parse("revert: feat: one\n")),
+            ("ccccccc3", parse("feat: one\n")),
+            ("ddddddd4", parse("revert: docs: gone\n\nRefs: 0000000\n")),
+        ];
+        let commits: Vec<_> = parsed
+            .iter()
+            .map(|(sha, commit)| (*sha, commit.as_commit()))
+            .collect();
+        // the revert of the revert brings `feat: one` back,
+        // the revert of the commit out of the range stays
+        assert_eq!(cancel_reverts(&commits), vec![&commits[2].1, &commits[3].1]);
+    }
+}
@@ -0,0 +1,319 @@
+use super::CommitFooter;
+use crate::bytes::Bytes;
+
+/// Separators recognised by default: the colon as git does, plus `#`
+/// for the `token #value` footers of the specification
+pub const SEPARATORS: &str = ":#";
+
+/// Lines generated by git itself, a paragraph is recognised as a trailer
+/// block with one of these even if it is mostly made of other lines
+const GIT_GENERATED: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+/// Trailers found in the last paragraph of the commit message, recognised
+/// the same way `git interpret-trailers` does
+#[derive(Debug, PartialEq, Eq)]
+pub struct Trailers<'c> {
+    /// The whole trailer block, from the first char of its first line
+    /// to the last char of its last line
+    pub bytes: Bytes,
+    pub footers: Vec<CommitFooter<'c>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line<'c> {
+    start: usize,
+    val: &'c str,
+}
+
+impl Line<'_> {
+    #[inline]
+    fn end(&self) -> usize {
+        self.start + self.val.len()
+    }
+
+    #[inline]
+    fn is_blank(&self) -> bool {
+        self.val.trim().is_empty()
+    }
+
+    #[inline]
+    fn is_continuation(&self) -> bool {
+        self.val.starts_with([' ', '\t'])
+    }
+}
+
+impl<'c> Trailers<'c> {
+    /// Find the trailer block within the commit message. It is only ever the
+    /// last paragraph, and never the first one (that is the header). The
+    /// paragraph is a trailer block when it is made of trailers only, or when
+    /// it has at least one git generated trailer and at least 25% of trailers.
+    /// The lines starting with the comment char are skipped, the way git
+    /// skips them whatever the cleanup mode.
+    ///
+    /// ```
+    /// use sven::{CommitFooter, Trailers, COMMENT, SEPARATORS};
+    ///
+    /// let commit = "fix: me\n\nCloses #42\nCo-authored-by: Jane <jane@example.com>\n";
+    /// let trailers = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+    /// assert_eq!(
+    ///     trailers.footers,
+    ///     vec![
+    ///         CommitFooter::Trailer("Closes", '#', "42"),
+    ///         CommitFooter::Simple("Co-authored-by", "Jane <jane@example.com>"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn parse(commit: &'c str, separators: &str, comment: char) -> Option<Self> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for val in commit.split_inclusive('\n') {
+            let line = val.trim_end_matches(['\n', '\r']);
+            if !line.starts_with(comment) {
+                lines.push(Line { start, val: line });
+            }
+            start += val.len();
+        }
+
+        while lines.last().is_some_and(Line::is_blank) {
+            lines.pop();
+        }
+
+        // the header is never a trailer block, so there must be
+        // a blank line somewhere before the last paragraph
+        let blank = lines.iter().rposition(Line::is_blank)?;
+        let block = &lines[blank + 1..];
+
+        let mut trailers: Vec<(Line, Option<(usize, char)>)> = Vec::new();
+        let mut trailer_lines = 0;
+        let mut non_trailer_lines = 0;
+        let mut recognized_prefix = false;
+        let mut prev_is_trailer = false;
+
+        for line in block {
+            if line.is_continuation() {
+                if prev_is_trailer {
+                    trailer_lines += 1;
+                    if let Some((last, _)) = trailers.last_mut() {
+                        last.val = &commit[last.start..line.end()];
+                    }
+                } else {
+                    non_trailer_lines += 1;
+                }
+                continue;
+            }
+
+            if GIT_GENERATED
+                .iter()
+                .any(|prefix| line.val.starts_with(prefix))
+            {
+                recognized_prefix = true;
+            }
+
+            let separator = find_separator(line.val, separators);
+            prev_is_trailer = separator.is_some() || line.val.starts_with(GIT_GENERATED[1]);
+
+            if prev_is_trailer {
+                trailer_lines += 1;
+                trailers.push((*line, separator));
+            } else {
+                non_trailer_lines += 1;
+            }
+        }
+
+        let is_block = trailer_lines > 0
+            && (non_trailer_lines == 0
+                || (recognized_prefix && trailer_lines * 3 >= non_trailer_lines));
+
+        if !is_block {
+            return None;
+        }
+
+        let first = block.first()?;
+        let last = block.last()?;
+
+        Some(Self {
+            bytes: Bytes::new(first.start, last.end()),
+            footers: trailers
+                .into_iter()
+                .filter_map(|(line, separator)| separator.map(|s| footer(line.val, s)))
+                .collect(),
+        })
+    }
+}
+
+fn footer(line: &str, (at, separator): (usize, char)) -> CommitFooter<'_> {
+    let token = line[..at].trim_end();
+    let value = line[at + separator.len_utf8()..].trim();
+
+    match (token, separator) {
+        ("BREAKING CHANGE" | "BREAKING-CHANGE", ':') => CommitFooter::BreakingChange(value),
+        (_, ':') => CommitFooter::Simple(token, value),
+        _ => CommitFooter::Trailer(token, separator, value),
+    }
+}
+
+/// Position of the separator when the line looks like a trailer: a token made
+/// of alphanumerics and `-`, optionally followed by whitespace, then one of the
+/// separators. `BREAKING CHANGE` is the only token allowed to contain a space.
+fn find_separator(line: &str, separators: &str) -> Option<(usize, char)> {
+    if let Some(rest) = line.strip_prefix("BREAKING CHANGE") {
+        let at = line.len() - rest.trim_start_matches([' ', '\t']).len();
+        return line[at..].starts_with(':').then_some((at, ':'));
+    }
+
+    let mut whitespace_found = false;
+
+    for (i, c) in line.char_indices() {
+        if separators.contains(c) {
+            return if i > 0 { Some((i, c)) } else { None };
+        }
+        if !whitespace_found && (c.is_ascii_alphanumeric() || c == '-') {
+            continue;
+        }
+        if i > 0 && (c == ' ' || c == '\t') {
+            whitespace_found = true;
+            continue;
+        }
+        break;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod parse {
+    use super::*;
+    use crate::cleanup::COMMENT;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn no_body() {
+        assert_eq!(Trailers::parse("Refs: #1", SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn simple() {
+        let commit = "fix: me\n\nRefs: #1001\nReviewed-by: Z\n";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(actual.bytes, Bytes::new(9, 35));
+        assert_eq!(
+            actual.footers,
+            vec![
+                CommitFooter::Simple("Refs", "#1001"),
+                CommitFooter::Simple("Reviewed-by", "Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn breaking_change() {
+        let commit = "fix: me\n\nBREAKING CHANGE: one\nBREAKING-CHANGE: two";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(
+            actual.footers,
+            vec![
+                CommitFooter::BreakingChange("one"),
+                CommitFooter::BreakingChange("two"),
+            ]
+        );
+    }
+
+    #[test]
+    fn hash() {
+        let commit = "fix: me\n\nCloses #42";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(
+            actual.footers,
+            vec![CommitFooter::Trailer("Closes", '#', "42")]
+        );
+    }
+
+    #[test]
+    fn hash_is_not_a_separator_for_git() {
+        let commit = "fix: me\n\nCloses #42";
+        assert_eq!(Trailers::parse(commit, ":", COMMENT), None);
+    }
+
+    #[test]
+    fn whitespace_before_separator() {
+        let commit = "fix: me\n\nAcked-by : X";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(actual.footers, vec![CommitFooter::Simple("Acked-by", "X")]);
+    }
+
+    #[test]
+    fn continuation() {
+        let commit = "fix: me\n\nBREAKING CHANGE: one\n  two\nRefs: #1";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(
+            actual.footers,
+            vec![
+                CommitFooter::BreakingChange("one\n  two"),
+                CommitFooter::Simple("Refs", "#1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_last_paragraph() {
+        let commit = "fix: me\n\nRefs: #1\n\nJust a body\n";
+        assert_eq!(Trailers::parse(commit, SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn mixed_without_git_generated() {
+        let commit = "fix: me\n\nsome text\nRefs: #1";
+        assert_eq!(Trailers::parse(commit, SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn mixed_with_git_generated() {
+        let commit = "fix: me\n\nsome text\nSigned-off-by: A <a@b.c>";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(
+            actual.footers,
+            vec![CommitFooter::Simple("Signed-off-by", "A <a@b.c>")]
+        );
+    }
+
+    #[test]
+    fn mixed_with_git_generated_too_few() {
+        let commit = "fix: me\n\none\ntwo\nthree\nfour\nSigned-off-by: A <a@b.c>";
+        assert_eq!(Trailers::parse(commit, SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn cherry_picked() {
+        let commit = "fix: me\n\n(cherry picked from commit abc)\nRefs: #1";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(actual.footers, vec![CommitFooter::Simple("Refs", "#1")]);
+    }
+
+    #[test]
+    fn comments() {
+        let commit = "fix: me\n\nRefs: #1\n\n# Please enter the commit message\n#\n";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(actual.footers, vec![CommitFooter::Simple("Refs", "#1")]);
+    }
+
+    #[test]
+    fn comment_char() {
+        let commit = "fix: me\n\nRefs: #1\n; left out\n";
+        let actual = Trailers::parse(commit, SEPARATORS, ';').unwrap();
+        assert_eq!(actual.footers, vec![CommitFooter::Simple("Refs", "#1")]);
+        assert_eq!(Trailers::parse(commit, SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn not_a_token() {
+        let commit = "fix: me\n\nsee http://example.com";
+        assert_eq!(Trailers::parse(commit, SEPARATORS, COMMENT), None);
+    }
+
+    #[test]
+    fn crlf() {
+        let commit = "fix: me\r\n\r\nRefs: #1\r\n";
+        let actual = Trailers::parse(commit, SEPARATORS, COMMENT).unwrap();
+        assert_eq!(actual.footers, vec![CommitFooter::Simple("Refs", "#1")]);
+    }
+}
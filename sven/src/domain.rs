@@ -1,6 +1,10 @@
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+use crate::message::{Locale, Message};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash, Default)]
 pub enum Domain {
     Root,
+    #[default]
     None,
     Type,
     Scope(Scope),
@@ -11,14 +15,45 @@ pub enum Domain {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+#[allow(clippy::enum_variant_names)]
 pub enum Scope {
     OpenBracket,
     Scope,
     CloseBracket,
 }
 
-impl Default for Domain {
-    fn default() -> Self {
-        Self::None
+impl Domain {
+    /// Id of the name of the domain within the message catalogs
+    pub fn id(&self) -> &'static str {
+        match self {
+            Domain::Root => "domain-root",
+            Domain::None => "domain-none",
+            Domain::Type => "domain-type",
+            Domain::Scope(Scope::OpenBracket) => "domain-scope-open",
+            Domain::Scope(Scope::Scope) => "domain-scope",
+            Domain::Scope(Scope::CloseBracket) => "domain-scope-close",
+            Domain::Breaking => "domain-breaking",
+            Domain::Colon => "domain-colon",
+            Domain::Space => "domain-space",
+            Domain::Desc => "domain-desc",
+        }
+    }
+
+    /// The only text blocks of the punctuation domains are expected to have
+    pub fn punctuation(&self) -> Option<&'static str> {
+        match self {
+            Domain::Scope(Scope::OpenBracket) => Some("("),
+            Domain::Scope(Scope::CloseBracket) => Some(")"),
+            Domain::Breaking => Some("!"),
+            Domain::Colon => Some(":"),
+            Domain::Space => Some(" "),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Domain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Message::new(self.id()).localize(Locale::En))
     }
 }
@@ -0,0 +1,77 @@
+use crate::message::{Arg, Locale, Message};
+use std::fmt::Display;
+
+/// Why the commit could not be analysed at all, as opposed to the issues
+/// found in it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Error {
+    /// The commit is not valid UTF-8, the bytes up to the given one are
+    InvalidUtf8(usize),
+
+    /// There is nothing to analyse after the cleanup
+    Empty,
+
+    /// The header is expected on its own, but a line ending is found
+    /// at the given byte
+    Multiline(usize),
+
+    /// The header takes more steps to analyse than allowed
+    BudgetExceeded(usize),
+
+    /// The commit is not written the way [`crate::ConventionalCommit`] is
+    /// displayed, the given thing is expected at the given byte
+    Syntax(usize, &'static str),
+
+    /// The given field of the commit being built can not be displayed
+    /// as the conventional commit
+    Invalid(&'static str),
+
+    /// The given setting of the config is not supported by the analysis yet
+    Unsupported(&'static str),
+
+    /// Something that should never happen did happen, that is a bug
+    Invariant(&'static str),
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    /// Message of the error, to be localised
+    pub fn message(&self) -> Message {
+        match self {
+            Error::InvalidUtf8(valid_up_to) => {
+                Message::new("error-invalid-utf8").arg("at", Arg::Number(*valid_up_to))
+            }
+            Error::Empty => Message::new("error-empty"),
+            Error::Multiline(at) => Message::new("error-multiline").arg("at", Arg::Number(*at)),
+            Error::BudgetExceeded(steps) => {
+                Message::new("error-budget-exceeded").arg("steps", Arg::Number(*steps))
+            }
+            Error::Syntax(at, expected) => Message::new("error-syntax")
+                .arg("expected", Arg::Text(expected.to_string()))
+                .arg("at", Arg::Number(*at)),
+            Error::Invalid(field) => {
+                Message::new("error-invalid").arg("field", Arg::Text(field.to_string()))
+            }
+            Error::Unsupported(what) => {
+                Message::new("error-unsupported").arg("what", Arg::Text(what.to_string()))
+            }
+            Error::Invariant(what) => {
+                Message::new("error-invariant").arg("what", Arg::Text(what.to_string()))
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message().localize(Locale::En))
+    }
+}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(e: std::str::Utf8Error) -> Self {
+        Error::InvalidUtf8(e.valid_up_to())
+    }
+}
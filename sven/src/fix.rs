@@ -0,0 +1,166 @@
+use crate::{block::Status, bytes::Bytes, config::Config, domain::Domain, report::Report};
+
+/// Replacement of the bytes of the commit with the text
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Edit {
+    pub bytes: Bytes,
+    pub text: &'static str,
+}
+
+impl Edit {
+    pub fn new(bytes: Bytes, text: &'static str) -> Self {
+        Self { bytes, text }
+    }
+}
+
+/// Commit with every issue of the header that can be fixed mechanically
+/// fixed, e.g. the missing colon is inserted and the full-width one is
+/// replaced with the ASCII one
+///
+/// ```
+/// use sven::{analyze, fix, Config};
+///
+/// let commit = "fix（app）：me";
+/// let report = analyze(commit, &Config::default()).unwrap();
+/// assert_eq!(fix(commit, &report), "fix(app): me");
+/// ```
+pub fn fix(commit: &str, report: &Report) -> String {
+    let mut edits: Vec<(Domain, Edit)> = report
+        .header
+        .iter()
+        .flat_map(|issue| {
+            report
+                .edits(issue)
+                .into_iter()
+                .map(|edit| (issue.domain(), edit))
+        })
+        .collect();
+    // insertions go before the replacements at the same byte, and
+    // insertions at the same byte go in the order of the header
+    edits.sort_by_key(|(domain, edit)| (edit.bytes.start(), edit.bytes.end(), *domain));
+
+    let mut fixed = String::with_capacity(commit.len());
+    let mut at = 0;
+    for (_, edit) in edits {
+        // edits overlapping the ones already applied are dropped
+        let Some(before) = commit.get(at..edit.bytes.start()) else {
+            continue;
+        };
+        fixed.push_str(before);
+        fixed.push_str(edit.text);
+        at = edit.bytes.end();
+    }
+    fixed.push_str(commit.get(at..).unwrap_or_default());
+
+    fixed
+}
+
+/// Commit with the type written as an alias replaced with the type it
+/// stands for, see [`Config::aliases`]
+///
+/// ```
+/// use sven::{analyze, canonicalize, Config};
+///
+/// let config = Config::parse("[aliases]\nbugfix = \"fix\"").unwrap();
+/// let commit = "bugfix(app): me";
+/// let report = analyze(commit, &config).unwrap();
+/// assert_eq!(canonicalize(commit, &report, &config), "fix(app): me");
+/// ```
+pub fn canonicalize(commit: &str, report: &Report, config: &Config) -> String {
+    let kind = report
+        .solution
+        .iter()
+        .find(|block| block.domain == Domain::Type && block.status == Status::Settled)
+        .and_then(|block| block.bytes);
+
+    match kind.and_then(|bytes| Some((bytes, bytes.capture(commit)?))) {
+        Some((bytes, alias)) => format!(
+            "{}{}{}",
+            &commit[..bytes.start()],
+            config.canonical(alias),
+            &commit[bytes.end()..]
+        ),
+        None => commit.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod apply {
+    use super::*;
+    use crate::{analyze::analyze, config::Config};
+    use pretty_assertions::assert_eq;
+
+    fn fixed(commit: &str) -> String {
+        fix(commit, &analyze(commit, &Config::default()).unwrap())
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(fixed("fix(app)!: me\n\nbody\n"), "fix(app)!: me\n\nbody\n");
+    }
+
+    #[test]
+    fn confusables() {
+        assert_eq!(fixed("fix（app）！：\u{a0}да"), "fix(app)!: да");
+    }
+
+    #[test]
+    fn missing() {
+        assert_eq!(fixed("fix me"), "fix: me");
+        assert_eq!(fixed("fix:me"), "fix: me");
+    }
+
+    #[test]
+    fn extra() {
+        assert_eq!(fixed("fix:  me"), "fix: me");
+        assert_eq!(fixed("fix(app)(: me"), "fix(app): me");
+    }
+
+    #[test]
+    fn strict_types() {
+        let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+        let fixed = |commit: &str| fix(commit, &analyze(commit, &config).unwrap());
+        assert_eq!(fixed("chore: me"), "chore: me");
+        assert_eq!(fixed("chore me"), "chore me");
+        assert_eq!(fixed("fix me"), "fix: me");
+        assert_eq!(fixed("fix（app）：me"), "fix(app): me");
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(fixed("  fix:\t me"), "fix: me");
+    }
+
+    #[test]
+    fn aliases() {
+        let config = Config::parse(
+            r#"
+types = ["feat", "fix"]
+
+[aliases]
+"исправление" = "fix"
+"✨" = "feat"
+"#,
+        )
+        .unwrap();
+        let canonical = |commit: &str| {
+            let fixed = fix(commit, &analyze(commit, &config).unwrap());
+            canonicalize(&fixed, &analyze(&fixed, &config).unwrap(), &config)
+        };
+        assert_eq!(canonical("исправление（ui）：да"), "fix(ui): да");
+        assert_eq!(canonical("✨ me\n\nbody\n"), "feat: me\n\nbody\n");
+        assert_eq!(canonical("feat: me"), "feat: me");
+    }
+
+    #[test]
+    fn fixed_is_valid() {
+        for commit in ["fix me", "fix（app）：me", "fix(app: me", "fix app): me"] {
+            let fixed = fixed(commit);
+            assert!(
+                analyze(&fixed, &Config::default()).unwrap().is_ok(),
+                "{}",
+                fixed
+            );
+        }
+    }
+}
@@ -1,4 +1,29 @@
 pub mod footer {
-    #[derive(Debug)]
-    pub struct Issue;
+    use crate::message::{Arg, Locale, Message};
+    use std::fmt::Display;
+
+    /// Something that is wrong with the footers of the commit
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[non_exhaustive]
+    pub enum Issue {
+        /// Footer with the token is required, but the commit does not have it
+        Missing(String),
+    }
+
+    impl Issue {
+        /// Message of the issue, to be localised
+        pub fn message(&self) -> Message {
+            match self {
+                Issue::Missing(token) => {
+                    Message::new("footer-missing").arg("token", Arg::Text(token.clone()))
+                }
+            }
+        }
+    }
+
+    impl Display for Issue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.message().localize(Locale::En))
+        }
+    }
 }
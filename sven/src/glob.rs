@@ -0,0 +1,107 @@
+/// Whether the path matches the glob: `**` matches any number of path
+/// segments, `*` matches within a single segment and `?` matches a single
+/// char of it
+pub fn matches(glob: &str, path: &str) -> bool {
+    let glob: Vec<&str> = glob.trim_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_matches('/').split('/').collect();
+    segments(&glob, &path)
+}
+
+/// Whether the segments match, backtracking to the last `**` only, which
+/// keeps the matching linear in the number of tries rather than exponential
+fn segments(glob: &[&str], path: &[&str]) -> bool {
+    let (mut g, mut p) = (0, 0);
+    // glob index after the last `**` along with the path index it resumes at
+    let mut star = None;
+    while p < path.len() {
+        if glob.get(g) == Some(&"**") {
+            star = Some((g + 1, p));
+            g += 1;
+        } else if glob.get(g).is_some_and(|glob| segment(glob, path[p])) {
+            g += 1;
+            p += 1;
+        } else if let Some((after, from)) = star {
+            star = Some((after, from + 1));
+            g = after;
+            p = from + 1;
+        } else {
+            return false;
+        }
+    }
+    glob[g..].iter().all(|glob| *glob == "**")
+}
+
+/// Whether the segment matches, the same way as [`segments`] but by char
+fn segment(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut g, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g + 1, n));
+                g += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after, from)) => {
+                    star = Some((after, from + 1));
+                    g = after;
+                    n = from + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod matches {
+    use super::*;
+
+    #[test]
+    fn literal() {
+        assert!(matches(
+            "packages/app/Cargo.toml",
+            "packages/app/Cargo.toml"
+        ));
+        assert!(!matches("packages/app", "packages/app/Cargo.toml"));
+    }
+
+    #[test]
+    fn star() {
+        assert!(matches("packages/*/Cargo.toml", "packages/app/Cargo.toml"));
+        assert!(matches("docs/*.md", "docs/readme.md"));
+        assert!(!matches("packages/*", "packages/app/Cargo.toml"));
+    }
+
+    #[test]
+    fn double_star() {
+        assert!(matches("packages/app/**", "packages/app/src/main.rs"));
+        assert!(matches("**/*.md", "README.md"));
+        assert!(matches("**/*.md", "docs/guide/intro.md"));
+        assert!(!matches("packages/app/**", "packages/ui/src/main.rs"));
+    }
+
+    #[test]
+    fn question_mark() {
+        assert!(matches("v?.md", "vя.md"));
+        assert!(!matches("v?.md", "v.md"));
+    }
+
+    #[test]
+    fn backtracking() {
+        let name = "a".repeat(64);
+        assert!(!matches("*a*a*a*a*a*a*a*a*a*a*b", &name));
+        assert!(matches("*a*a*a*a*a*a*a*a*a*a", &name));
+
+        let path = ["a"; 64].join("/");
+        assert!(!matches("**/a/**/a/**/a/**/a/**/a/**/b", &path));
+        assert!(matches("**/a/**/a/**/a/**/a/**/a/**", &path));
+    }
+}
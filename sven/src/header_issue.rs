@@ -0,0 +1,228 @@
+pub mod header {
+    use crate::{
+        block::{Block, Status, Val},
+        bytes::Bytes,
+        domain::Domain,
+        fix::Edit,
+        message::{Arg, Locale, Message},
+    };
+    use std::fmt::Display;
+
+    /// Something that is wrong with the header of the commit, found in the
+    /// solution picked by the analysis
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub enum Issue {
+        /// Block of the domain is missing, expected to be at the given byte
+        Missing(Domain, usize),
+
+        /// Block is not expected at all
+        Extra(Domain, Bytes),
+
+        /// Block is found at the bytes, but it is expected to be at the given byte
+        Misplaced(Domain, Bytes, usize),
+
+        /// Block is in its place, but its value is not one of the allowed ones
+        Unknown(Domain, Bytes),
+
+        /// Block is in its place, but its value does not match the changed paths
+        Mismatch(Domain, Bytes),
+
+        /// Block is in its place, but it is a look-alike of the expected
+        /// punctuation, e.g. a full-width colon or a no-break space
+        WrongChar(Domain, Bytes),
+
+        /// Block is in its place, but it is a run of whitespace instead
+        /// of exactly one space
+        SpaceRun(Domain, Bytes),
+
+        /// Whitespace the header starts with
+        Leading(Domain, Bytes),
+
+        /// Whitespace the header ends with
+        Trailing(Domain, Bytes),
+    }
+
+    impl Issue {
+        /// Collect issues from the solution picked by the analysis
+        pub fn collect(solution: &[Block]) -> Vec<Issue> {
+            let mut issues = Vec::new();
+
+            for (i, block) in solution.iter().enumerate() {
+                match (block.status, block.bytes) {
+                    (Status::Missing, _) => {
+                        issues.push(Issue::Missing(block.domain, at(solution, i)));
+                    }
+                    (Status::Extra, Some(bytes)) if i == 1 && block.val == Val::Space => {
+                        issues.push(Issue::Leading(block.domain, bytes));
+                    }
+                    (Status::Extra, Some(bytes)) => {
+                        issues.push(Issue::Extra(block.domain, bytes));
+                    }
+                    (Status::Ref(portal), Some(bytes)) => {
+                        issues.push(Issue::Misplaced(block.domain, bytes, at(solution, portal)));
+                    }
+                    _ => {}
+                }
+            }
+
+            issues
+        }
+
+        /// Same issue with its bytes mapped by the given function
+        pub fn map(&self, f: impl Fn(Bytes) -> Bytes) -> Issue {
+            let at = |at: usize| f(Bytes::empty_at(at)).start();
+            match *self {
+                Issue::Missing(domain, pos) => Issue::Missing(domain, at(pos)),
+                Issue::Extra(domain, bytes) => Issue::Extra(domain, f(bytes)),
+                Issue::Misplaced(domain, bytes, pos) => Issue::Misplaced(domain, f(bytes), at(pos)),
+                Issue::Unknown(domain, bytes) => Issue::Unknown(domain, f(bytes)),
+                Issue::Mismatch(domain, bytes) => Issue::Mismatch(domain, f(bytes)),
+                Issue::WrongChar(domain, bytes) => Issue::WrongChar(domain, f(bytes)),
+                Issue::SpaceRun(domain, bytes) => Issue::SpaceRun(domain, f(bytes)),
+                Issue::Leading(domain, bytes) => Issue::Leading(domain, f(bytes)),
+                Issue::Trailing(domain, bytes) => Issue::Trailing(domain, f(bytes)),
+            }
+        }
+
+        pub fn domain(&self) -> Domain {
+            match self {
+                Issue::Missing(domain, _) => *domain,
+                Issue::Extra(domain, _) => *domain,
+                Issue::Misplaced(domain, _, _) => *domain,
+                Issue::Unknown(domain, _) => *domain,
+                Issue::Mismatch(domain, _) => *domain,
+                Issue::WrongChar(domain, _) => *domain,
+                Issue::SpaceRun(domain, _) => *domain,
+                Issue::Leading(domain, _) => *domain,
+                Issue::Trailing(domain, _) => *domain,
+            }
+        }
+
+        /// Edits of the commit fixing the issue, none when it
+        /// cannot be fixed without the author
+        pub fn edits(&self) -> Vec<Edit> {
+            let text = |domain: Domain| domain.punctuation();
+            match *self {
+                Issue::Missing(domain, at) => text(domain)
+                    .map(|text| Edit::new(Bytes::empty_at(at), text))
+                    .into_iter()
+                    .collect(),
+                Issue::Extra(_, bytes) => vec![Edit::new(bytes, "")],
+                Issue::Misplaced(domain, bytes, at) => match text(domain) {
+                    Some(text) => vec![Edit::new(Bytes::empty_at(at), text), Edit::new(bytes, "")],
+                    None => Vec::new(),
+                },
+                Issue::WrongChar(domain, bytes) | Issue::SpaceRun(domain, bytes) => text(domain)
+                    .map(|text| Edit::new(bytes, text))
+                    .into_iter()
+                    .collect(),
+                Issue::Leading(_, bytes) | Issue::Trailing(_, bytes) => vec![Edit::new(bytes, "")],
+                Issue::Unknown(..) | Issue::Mismatch(..) => Vec::new(),
+            }
+        }
+
+        /// Bytes in the original commit this issue is about, empty when
+        /// pointing at the place where something is missing
+        pub fn bytes(&self) -> Bytes {
+            match self {
+                Issue::Missing(_, at) => Bytes::empty_at(*at),
+                Issue::Extra(_, bytes) => *bytes,
+                Issue::Misplaced(_, bytes, _) => *bytes,
+                Issue::Unknown(_, bytes) => *bytes,
+                Issue::Mismatch(_, bytes) => *bytes,
+                Issue::WrongChar(_, bytes) => *bytes,
+                Issue::SpaceRun(_, bytes) => *bytes,
+                Issue::Leading(_, bytes) => *bytes,
+                Issue::Trailing(_, bytes) => *bytes,
+            }
+        }
+    }
+
+    impl Issue {
+        /// Message of the issue, to be localised
+        pub fn message(&self) -> Message {
+            let of = |id, domain: &Domain| Message::new(id).arg("domain", Arg::Domain(*domain));
+            match self {
+                Issue::Missing(domain, _) => of("header-missing", domain),
+                Issue::Extra(domain, _) => of("header-extra", domain),
+                Issue::Misplaced(domain, _, at) => {
+                    of("header-misplaced", domain).arg("at", Arg::Number(*at))
+                }
+                Issue::Unknown(domain, _) => of("header-unknown", domain),
+                Issue::Mismatch(domain, _) => of("header-mismatch", domain),
+                Issue::WrongChar(domain, _) => match domain.punctuation() {
+                    Some(expected) => Message::new("header-wrong-char")
+                        .arg("expected", Arg::Text(format!("{:?}", expected))),
+                    None => of("header-wrong-char-of", domain),
+                },
+                Issue::SpaceRun(Domain::Space, _) => Message::new("header-space-after-colon"),
+                Issue::SpaceRun(domain, _) => of("header-space-run", domain),
+                Issue::Leading(..) => Message::new("header-leading"),
+                Issue::Trailing(..) => Message::new("header-trailing"),
+            }
+        }
+    }
+
+    /// Byte where the block without bytes of its own is located
+    fn at(solution: &[Block], i: usize) -> usize {
+        solution[..i]
+            .iter()
+            .rev()
+            .find_map(|block| block.bytes)
+            .map(|bytes| bytes.end())
+            .unwrap_or_default()
+    }
+
+    impl Display for Issue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.message().localize(Locale::En))
+        }
+    }
+
+    #[cfg(test)]
+    mod collect {
+        use super::*;
+        use crate::{analyze_header::analyze_header, config::Config, weak_commit::WeakCommit};
+        use pretty_assertions::assert_eq;
+
+        fn issues(commit: &str) -> Vec<Issue> {
+            let w = WeakCommit::parse(commit);
+            Issue::collect(&analyze_header(commit, &Config::default(), w.header).unwrap())
+        }
+
+        #[test]
+        fn none() {
+            assert_eq!(issues("fix(app)!: me"), vec![]);
+        }
+
+        #[test]
+        fn missing() {
+            assert_eq!(issues("fix me"), vec![Issue::Missing(Domain::Colon, 3)]);
+        }
+
+        #[test]
+        fn extra() {
+            assert_eq!(
+                issues("fix (app): me"),
+                vec![Issue::Extra(Domain::Space, Bytes::new(3, 4))]
+            );
+        }
+
+        #[test]
+        fn leading() {
+            assert_eq!(
+                issues(" fix: me"),
+                vec![Issue::Leading(Domain::Space, Bytes::new(0, 1))]
+            );
+        }
+
+        #[test]
+        fn misplaced() {
+            assert_eq!(
+                issues("fix :me"),
+                vec![Issue::Misplaced(Domain::Colon, Bytes::new(4, 5), 3)]
+            );
+        }
+    }
+}
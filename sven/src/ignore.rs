@@ -0,0 +1,77 @@
+use crate::config::Config;
+use regex::Regex;
+
+/// Headers of the commits written by git, or by the release tooling, which
+/// are ignored on top of the ones from the config
+pub const IGNORED: [&str; 3] = [
+    r"^Merge (branch|remote-tracking branch|pull request|tag|commit) ",
+    r"^amend! ",
+    r"^chore\(release\): ",
+];
+
+/// Prefixes of the headers `git commit --fixup` and `--squash` write in front
+/// of the header of the commit they are going to be squashed into
+const WRAPPERS: [&str; 2] = ["fixup! ", "squash! "];
+
+/// Patterns of [`IGNORED`], compiled
+pub fn ignored() -> Vec<Regex> {
+    IGNORED
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("valid pattern"))
+        .collect()
+}
+
+/// Whether the commit with the header is not analysed at all
+pub(crate) fn is_ignored(header: &str, config: &Config) -> bool {
+    config.ignore.iter().any(|pattern| pattern.is_match(header))
+}
+
+/// Length of the `fixup! ` and `squash! ` prefixes the header starts with,
+/// the header they wrap is analysed in place of the whole one
+pub(crate) fn wrapper_len(header: &str) -> usize {
+    let mut rest = header;
+    while let Some(wrapped) = WRAPPERS
+        .iter()
+        .find_map(|wrapper| rest.strip_prefix(wrapper))
+    {
+        rest = wrapped;
+    }
+    header.len() - rest.len()
+}
+
+#[cfg(test)]
+mod patterns {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn defaults() {
+        let config = Config::default();
+        for header in [
+            "Merge branch 'feature'",
+            "Merge branch 'feature' into main",
+            "Merge remote-tracking branch 'origin/main'",
+            "Merge pull request #42 from me/feature",
+            "Merge tag 'v1.0.0'",
+            "amend! feat: me",
+            "chore(release): 1.2.3",
+        ] {
+            assert!(is_ignored(header, &config), "{}", header);
+        }
+        for header in [
+            "Merge conflicts are resolved",
+            "chore: release",
+            "fixup! feat: me",
+        ] {
+            assert!(!is_ignored(header, &config), "{}", header);
+        }
+    }
+
+    #[test]
+    fn wrappers() {
+        assert_eq!(wrapper_len("feat: me"), 0);
+        assert_eq!(wrapper_len("fixup! feat: me"), 7);
+        assert_eq!(wrapper_len("squash! fixup! feat: me"), 15);
+        assert_eq!(wrapper_len("fixup!feat: me"), 0);
+    }
+}
@@ -0,0 +1,87 @@
+//! Conventional Commits linter
+//!
+//! The entry point is [`analyze`] which takes a commit message and reports
+//! every issue found in it according to the [`Config`].
+//!
+//! ```
+//! use sven::{analyze, header, Config, Domain};
+//!
+//! let report = analyze("fix a bug", &Config::default()).unwrap();
+//! assert_eq!(report.header, vec![header::Issue::Missing(Domain::Colon, 3)]);
+//! ```
+
+mod analyze;
+mod analyze_branch;
+mod analyze_header;
+mod analyze_paths;
+mod analyze_title;
+mod block;
+#[cfg(test)]
+mod block_factory;
+mod branch_issue;
+mod bump;
+mod bytes;
+mod changelog;
+mod cleanup;
+mod config;
+mod conventional_commit;
+mod domain;
+mod error;
+mod fix;
+mod footer_issue;
+mod glob;
+mod header_issue;
+mod ignore;
+mod message;
+mod position;
+#[cfg(test)]
+mod properties;
+mod report;
+mod shape_issue;
+mod template;
+mod weak_commit;
+
+pub use analyze::{analyze, analyze_bytes};
+pub use analyze_branch::analyze_branch;
+pub use analyze_paths::analyze_paths;
+pub use analyze_title::analyze_title;
+pub use block::{Block, Status, Val};
+pub use branch_issue::branch;
+pub use bump::Bump;
+pub use bytes::Bytes;
+pub use changelog::{changelog, prepend, Layout, Section};
+pub use cleanup::{Cleaned, Cleanup, COMMENT};
+pub use config::{Config, ScopeRule, TypeRule, CONFIG_FILE};
+pub use conventional_commit::{
+    cancel_reverts, CommitBuilder, CommitFooter, CommitHeader, ConventionalCommit,
+    OwnedCommitFooter, OwnedCommitHeader, OwnedConventionalCommit, ParsedCommit, Revert, Trailers,
+    SEPARATORS,
+};
+pub use domain::{Domain, Scope};
+pub use error::Error;
+pub use fix::{canonicalize, fix, Edit};
+pub use footer_issue::footer;
+pub use header_issue::header;
+pub use ignore::{ignored, IGNORED};
+pub use message::{Arg, Locale, Message};
+pub use position::{Encoding, Position, PositionIndex};
+pub use report::Report;
+pub use shape_issue::shape;
+pub use template::template;
+pub use weak_commit::{Row, WeakCommit};
+
+/// Internals exposed to the fuzz targets only
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing {
+    pub use crate::analyze_header::analyze_header;
+    pub use crate::weak_commit::parse_header::parse_header;
+}
+
+/// The pest implementation of [`WeakCommit::parse`] the scanner replaced,
+/// for the benchmark only
+#[cfg(feature = "baseline")]
+#[doc(hidden)]
+pub mod baseline {
+    pub use crate::weak_commit::baseline::parse;
+}
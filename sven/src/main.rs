@@ -1,20 +1,51 @@
-#[macro_use]
-extern crate pest_derive;
+use std::process::ExitCode;
+use sven::{template, Config, CONFIG_FILE};
 
-mod additive;
-mod analyze;
-mod analyze_header;
-mod block;
-mod block_factory;
-mod bytes;
-mod config;
-mod conventional_commit;
-mod domain;
-mod footer_issue;
-mod report;
-use analyze::analyze;
-mod weak_commit;
+mod cli;
 
-fn main() {
-    analyze("hello world").expect("invalid commit mate");
+const USAGE: &str = "\
+usage: sven [<file>]                analyze the commit message from the file, or stdin
+       sven --fix [<file>]          fix what can be fixed in the file, or print the fixed stdin
+       sven template                print the commit template, see git's commit.template
+       sven commit [<git args>...]  compose the commit interactively and run git commit
+       sven bump [<range>]          print the next version, from the commits since the latest tag
+       sven changelog [<range>] [--keep-a-changelog] [--write]
+                                    print the changelog of the commits since the latest tag,
+                                    or prepend it to CHANGELOG.md with --write
+       sven pre-receive             analyze the pushed commits, as git's pre-receive hook
+       sven branch [<name>]         analyze the name of the branch, or of the current one
+       sven title [--format <text|json>] [--] <title>
+                                    analyze the title of the pull request as the header
+       sven lsp                     run the language server over stdio, for the editors";
+
+fn main() -> ExitCode {
+    let config = match Config::load(CONFIG_FILE) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("could not read {}: {}", CONFIG_FILE, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Some("template") => {
+            print!("{}", template(&config));
+            ExitCode::SUCCESS
+        }
+        Some("commit") => cli::commit(&config, &args[1..]),
+        Some("changelog") => cli::changelog(&config, &args[1..]),
+        Some("lsp") => cli::lsp(&config),
+        Some("title") => cli::title(&config, &args[1..]),
+        Some("branch") => cli::branch(&config, args.get(1).map(String::as_str)),
+        Some("pre-receive") => cli::pre_receive(&config),
+        Some("bump") => cli::bump(&config, args.get(1).map(String::as_str)),
+        Some("--fix") => cli::check(&config, args.get(1).map(String::as_str), true),
+        path => cli::check(&config, path, false),
+    }
 }
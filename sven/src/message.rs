@@ -0,0 +1,216 @@
+use crate::domain::Domain;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+/// Language of the messages
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    /// Locale of the POSIX locale name, e.g. `ru_RU.UTF-8`, English when
+    /// there is no catalog for the language
+    ///
+    /// ```
+    /// use sven::Locale;
+    ///
+    /// assert_eq!(Locale::from_posix("ru_RU.UTF-8"), Locale::Ru);
+    /// assert_eq!(Locale::from_posix("C"), Locale::En);
+    /// ```
+    pub fn from_posix(name: &str) -> Self {
+        let language = name.split(['_', '.', '@']).next().unwrap_or_default();
+        match language {
+            "ru" => Locale::Ru,
+            _ => Locale::En,
+        }
+    }
+
+    /// Locale of the environment, the first of `LC_ALL`, `LC_MESSAGES` and
+    /// `LANG` that is set, the same order gettext reads them in
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Locale::En, |value| Self::from_posix(&value))
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.catalog"),
+            Locale::Ru => include_str!("../locales/ru.catalog"),
+        }
+    }
+
+    /// Messages of the locale by their ids, parsed once
+    fn catalog(&self) -> &'static HashMap<&'static str, &'static str> {
+        static EN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static RU: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        let catalog = match self {
+            Locale::En => &EN,
+            Locale::Ru => &RU,
+        };
+        catalog.get_or_init(|| parse(self.source()))
+    }
+}
+
+/// Lines of the catalog, `id = text`, skipping the blank ones and the comments
+fn parse(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(id, text)| (id.trim(), text.trim()))
+        .collect()
+}
+
+/// Value of the placeable of the message
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Arg {
+    Number(usize),
+    Text(String),
+
+    /// Name of the domain, localised along with the message
+    Domain(Domain),
+}
+
+/// Message of the issue, the id within the catalogs along with the values
+/// of its placeables, see [`Message::localize`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Message {
+    pub id: &'static str,
+    pub args: Vec<(&'static str, Arg)>,
+}
+
+impl Message {
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, name: &'static str, value: Arg) -> Self {
+        self.args.push((name, value));
+        self
+    }
+
+    /// Text of the message in the locale, falling back to English when the
+    /// catalog of the locale does not have it yet
+    ///
+    /// ```
+    /// use sven::{analyze, Config, Locale};
+    ///
+    /// let report = analyze("fix me", &Config::default()).unwrap();
+    /// let message = report.header[0].message();
+    /// assert_eq!(message.localize(Locale::En), "missing colon");
+    /// assert_eq!(message.localize(Locale::Ru), "отсутствует двоеточие");
+    /// ```
+    pub fn localize(&self, locale: Locale) -> String {
+        let template = text(self.id, locale);
+
+        let mut localized = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some((before, after)) = rest.split_once('{') {
+            localized.push_str(before);
+            let Some((placeable, after)) = after.split_once('}') else {
+                rest = after;
+                break;
+            };
+            let name = placeable.trim().trim_start_matches('$');
+            match self.args.iter().find(|(arg, _)| *arg == name) {
+                Some((_, Arg::Number(number))) => localized.push_str(&number.to_string()),
+                Some((_, Arg::Text(text))) => localized.push_str(text),
+                Some((_, Arg::Domain(domain))) => localized.push_str(text(domain.id(), locale)),
+                None => localized.push_str(name),
+            }
+            rest = after;
+        }
+        localized.push_str(rest);
+
+        localized
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localize(Locale::En))
+    }
+}
+
+/// Text of the id in the locale, then in English, then the id itself
+fn text(id: &'static str, locale: Locale) -> &'static str {
+    locale
+        .catalog()
+        .get(id)
+        .or_else(|| Locale::En.catalog().get(id))
+        .copied()
+        .unwrap_or(id)
+}
+
+#[cfg(test)]
+mod catalogs {
+    use super::*;
+    use crate::error::Error;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn same_ids() {
+        let ids = |locale: Locale| {
+            let mut ids: Vec<&str> = locale.catalog().keys().copied().collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(Locale::Ru), ids(Locale::En));
+    }
+
+    #[test]
+    fn placeables() {
+        let message = Message::new("header-misplaced")
+            .arg("domain", Arg::Domain(Domain::Colon))
+            .arg("at", Arg::Number(3));
+        assert_eq!(
+            message.localize(Locale::En),
+            "misplaced colon, expected at byte 3"
+        );
+        assert_eq!(
+            message.localize(Locale::Ru),
+            "двоеточие не на своём месте, ожидается в байте 3"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = Error::Unsupported("`TypeRule::Like`");
+        assert_eq!(error.to_string(), "`TypeRule::Like` is not supported yet");
+        assert_eq!(
+            error.message().localize(Locale::Ru),
+            "`TypeRule::Like` пока не поддерживается"
+        );
+        assert_eq!(
+            Error::Syntax(4, "`: `").message().localize(Locale::Ru),
+            "ожидается `: ` в байте 4"
+        );
+    }
+
+    #[test]
+    fn unknown_id() {
+        assert_eq!(
+            Message::new("header-typo").localize(Locale::Ru),
+            "header-typo"
+        );
+    }
+
+    #[test]
+    fn posix() {
+        assert_eq!(Locale::from_posix("ru"), Locale::Ru);
+        assert_eq!(Locale::from_posix("ru_UA@euro"), Locale::Ru);
+        assert_eq!(Locale::from_posix("en_US.UTF-8"), Locale::En);
+        assert_eq!(Locale::from_posix("rust"), Locale::En);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{
+    bytes::Bytes,
+    weak_commit::{Row, WeakCommit},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What the column of the [`Position`] is counted in
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Encoding {
+    /// Bytes, LSP's `utf-8`
+    Utf8,
+
+    /// Unicode scalar values, LSP's `utf-32`
+    Chars,
+
+    /// UTF-16 code units, LSP's default `utf-16`
+    #[default]
+    Utf16,
+
+    /// Extended grapheme clusters, what the author sees as characters
+    Graphemes,
+}
+
+/// Row and column of the byte of the commit
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Position {
+    /// The row starting 1, the same as [`Row::row`]
+    pub row: usize,
+
+    /// Units of the row in front of the byte, starting 0
+    pub col: usize,
+}
+
+/// Positions of the bytes of the commit, built once from its rows
+///
+/// ```
+/// use sven::{Bytes, Encoding, Position, PositionIndex};
+///
+/// let index = PositionIndex::new("fix: да\n🚀 me");
+/// let at = |row, col| Position { row, col };
+/// assert_eq!(index.position(9, Encoding::Chars), at(1, 7));
+/// assert_eq!(
+///     index.range(Bytes::new(10, 15), Encoding::Utf16),
+///     (at(2, 0), at(2, 3))
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PositionIndex<'c> {
+    commit: &'c str,
+    rows: Vec<Row>,
+}
+
+impl<'c> PositionIndex<'c> {
+    pub fn new(commit: &'c str) -> Self {
+        Self::from_rows(commit, WeakCommit::parse(commit).rows)
+    }
+
+    /// Index of the rows of the commit, see [`WeakCommit::rows`]
+    pub fn from_rows(commit: &'c str, rows: Vec<Row>) -> Self {
+        Self { commit, rows }
+    }
+
+    /// Position of the byte, the byte within the line ending is the end of
+    /// the row, and the byte past the commit is the end of the commit
+    pub fn position(&self, at: usize, encoding: Encoding) -> Position {
+        let at = floor_char_boundary(self.commit, at.min(self.commit.len()));
+
+        // the row after the last line ending is not among the rows
+        let next = self.rows.last().map_or(0, |row| row.bytes.end());
+        if at >= next && (next == 0 || self.commit[..next].ends_with(['\n', '\r'])) {
+            return Position {
+                row: self.rows.len() + 1,
+                col: 0,
+            };
+        }
+
+        let index = self
+            .rows
+            .partition_point(|row| row.bytes.start() <= at)
+            .saturating_sub(1);
+        let row = &self.rows[index];
+        let start = row.bytes.start();
+        let end = self.content_end(row);
+        let text = &self.commit[start..at.min(end)];
+
+        Position {
+            row: row.row,
+            col: match encoding {
+                Encoding::Utf8 => text.len(),
+                Encoding::Chars => text.chars().count(),
+                Encoding::Utf16 => text.chars().map(char::len_utf16).sum(),
+                // the byte within the cluster is the start of the cluster
+                Encoding::Graphemes => self.commit[start..end]
+                    .grapheme_indices(true)
+                    .take_while(|(at, grapheme)| at + grapheme.len() <= text.len())
+                    .count(),
+            },
+        }
+    }
+
+    /// Positions of the start and the end of the bytes
+    pub fn range(&self, bytes: Bytes, encoding: Encoding) -> (Position, Position) {
+        (
+            self.position(bytes.start(), encoding),
+            self.position(bytes.end(), encoding),
+        )
+    }
+
+    /// Byte at the position, the inverse of [`PositionIndex::position`]. The
+    /// column past the end of the row is the end of the row, and the column
+    /// within the unit is the start of the unit
+    pub fn offset(&self, position: Position, encoding: Encoding) -> usize {
+        let Some(row) = self.rows.get(position.row.wrapping_sub(1)) else {
+            return self.commit.len();
+        };
+        let start = row.bytes.start();
+        let end = self.content_end(row);
+        let text = &self.commit[start..end];
+
+        let at = match encoding {
+            Encoding::Utf8 => Some(floor_char_boundary(text, position.col.min(text.len()))),
+            Encoding::Chars => text.char_indices().nth(position.col).map(|(at, _)| at),
+            Encoding::Utf16 => {
+                let mut units = 0;
+                text.char_indices().find_map(|(at, c)| {
+                    units += c.len_utf16();
+                    (units > position.col).then_some(at)
+                })
+            }
+            Encoding::Graphemes => text
+                .grapheme_indices(true)
+                .nth(position.col)
+                .map(|(at, _)| at),
+        };
+
+        start + at.unwrap_or(text.len())
+    }
+
+    /// End of the row without its line ending
+    fn content_end(&self, row: &Row) -> usize {
+        let text = &self.commit[row.bytes.start()..row.bytes.end()];
+        row.bytes.start() + text.trim_end_matches(['\n', '\r']).len()
+    }
+}
+
+fn floor_char_boundary(text: &str, mut at: usize) -> usize {
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+#[cfg(test)]
+mod index {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    fn columns(index: &PositionIndex, byte: usize) -> [usize; 4] {
+        [
+            Encoding::Utf8,
+            Encoding::Chars,
+            Encoding::Utf16,
+            Encoding::Graphemes,
+        ]
+        .map(|encoding| index.position(byte, encoding).col)
+    }
+
+    #[test]
+    fn cyrillic() {
+        let index = PositionIndex::new("исправление: да\nтело");
+        assert_eq!(columns(&index, 22), [22, 11, 11, 11]);
+        assert_eq!(index.position(29, Encoding::Utf16), at(2, 0));
+        assert_eq!(index.position(33, Encoding::Chars), at(2, 2));
+    }
+
+    #[test]
+    fn cjk() {
+        let index = PositionIndex::new("修复（界面）：按钮");
+        // the full-width colon
+        assert_eq!(columns(&index, 18), [18, 6, 6, 6]);
+        assert_eq!(columns(&index, 27), [27, 9, 9, 9]);
+    }
+
+    #[test]
+    fn emoji() {
+        // the flag is two regional indicators, the family is joined with ZWJ
+        let commit = "✨ 🚀 🇺🇦 👨‍👩‍👧: me";
+        let index = PositionIndex::new(commit);
+        let colon = commit.find(':').unwrap();
+        assert_eq!(columns(&index, 4), [4, 2, 2, 2]);
+        assert_eq!(columns(&index, 9), [9, 4, 5, 4]);
+        assert_eq!(columns(&index, colon), [colon, 12, 18, 7]);
+    }
+
+    #[test]
+    fn combining() {
+        // "й" as "и" with the combining breve
+        let index = PositionIndex::new("и\u{306}: да");
+        assert_eq!(columns(&index, 4), [4, 2, 2, 1]);
+    }
+
+    #[test]
+    fn line_endings() {
+        let commit = "fix: me\r\nbody\rmore\n";
+        let index = PositionIndex::new(commit);
+        // within the line ending
+        assert_eq!(index.position(8, Encoding::Chars), at(1, 7));
+        assert_eq!(index.position(9, Encoding::Chars), at(2, 0));
+        assert_eq!(index.position(14, Encoding::Chars), at(3, 0));
+        assert_eq!(index.position(commit.len(), Encoding::Chars), at(4, 0));
+        assert_eq!(index.position(99, Encoding::Chars), at(4, 0));
+    }
+
+    #[test]
+    fn empty() {
+        let index = PositionIndex::new("");
+        assert_eq!(index.position(0, Encoding::Utf16), at(1, 0));
+        assert_eq!(index.offset(at(1, 0), Encoding::Utf16), 0);
+    }
+
+    #[test]
+    fn within_char() {
+        let index = PositionIndex::new("да");
+        assert_eq!(index.position(1, Encoding::Chars), at(1, 0));
+        assert_eq!(index.position(3, Encoding::Utf8), at(1, 2));
+    }
+
+    #[test]
+    fn range() {
+        let commit = "fix（界面）: 🚀";
+        let index = PositionIndex::new(commit);
+        let bytes = Bytes::new(3, 6);
+        assert_eq!(index.range(bytes, Encoding::Utf16), (at(1, 3), at(1, 4)));
+    }
+
+    #[test]
+    fn offsets() {
+        let commit = "✨ 🇺🇦 да\r\nя\n";
+        let index = PositionIndex::new(commit);
+        for encoding in [
+            Encoding::Utf8,
+            Encoding::Chars,
+            Encoding::Utf16,
+            Encoding::Graphemes,
+        ] {
+            for (byte, _) in commit.char_indices() {
+                let position = index.position(byte, encoding);
+                let offset = index.offset(position, encoding);
+                // the position within the grapheme or the line ending is
+                // the start of the grapheme or the end of the row
+                assert!(offset <= byte, "{:?} {} {}", encoding, byte, offset);
+                assert_eq!(index.position(offset, encoding), position);
+            }
+        }
+        assert_eq!(index.offset(at(1, 99), Encoding::Chars), 17);
+        assert_eq!(index.offset(at(2, 0), Encoding::Utf16), 19);
+        assert_eq!(index.offset(at(3, 0), Encoding::Utf16), commit.len());
+        // the column within the surrogate pair
+        assert_eq!(index.offset(at(1, 3), Encoding::Utf16), 4);
+    }
+}
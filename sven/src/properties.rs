@@ -0,0 +1,154 @@
+//! Properties of the analysis which hold for any input
+
+use crate::{
+    analyze::analyze,
+    config::Config,
+    conventional_commit::{CommitFooter, CommitHeader, ConventionalCommit, ParsedCommit},
+    domain::Domain,
+    fix::fix,
+    header_issue::header,
+    report::Report,
+};
+use proptest::prelude::*;
+
+/// Mostly the chars the header is made of, including the look-alikes
+const CHARS: &str = "[a-zа-я漢🚀 :()!#\t\n\r：（）！\u{a0}]{0,40}";
+
+/// Headers with every piece in place, the punctuation and the whitespace
+/// aside, which are the issues the fix copes with on its own
+const FIXABLE: &str =
+    "[a-z]{1,8}([(（][a-z]{1,8}[)）])?[!！]?([:：][ \u{a0}]{0,2}|[ \u{a0}]{1,2})[a-z]{1,8}( [a-z]{1,8}){0,3}";
+
+const WORD: &str = "[a-zA-Zа-яА-Я0-9漢🚀]{1,8}";
+
+fn header() -> impl Strategy<Value = (String, Option<String>, bool, String)> {
+    (
+        "[a-z]{1,8}",
+        proptest::option::of("[a-z]{1,8}"),
+        any::<bool>(),
+        proptest::collection::vec(WORD, 1..6).prop_map(|words| words.join(" ")),
+    )
+}
+
+fn body() -> impl Strategy<Value = Option<String>> {
+    proptest::option::of(proptest::collection::vec(WORD, 1..20).prop_map(|words| words.join(" ")))
+}
+
+fn footers() -> impl Strategy<Value = Vec<(String, String)>> {
+    proptest::collection::vec(("[A-Z][a-z]{1,8}", WORD), 0..3)
+}
+
+/// Footers of every kind, the breaking change ones spanning a few lines, and
+/// the trailers with any separator but the colon, which is the simple footer
+fn any_footers() -> impl Strategy<Value = Vec<(u8, String, char, String)>> {
+    let value = proptest::collection::vec(WORD, 1..4).prop_map(|words| words.join("\n  "));
+    let separator = proptest::sample::select(vec!['#', '=', '>', '%', '~']);
+    proptest::collection::vec((0..3u8, "[A-Z][a-z-]{0,8}", separator, value), 0..4)
+}
+
+proptest! {
+    #[test]
+    fn bytes_within_the_input(commit in CHARS) {
+        if let Ok(report) = analyze(&commit, &Config::default()) {
+            for bytes in report.solution.iter().filter_map(|block| block.bytes) {
+                prop_assert!(bytes.end() <= commit.len());
+                prop_assert!(commit.is_char_boundary(bytes.start()));
+                prop_assert!(commit.is_char_boundary(bytes.end()));
+            }
+            for issue in &report.header {
+                prop_assert!(commit.is_char_boundary(issue.bytes().start()));
+                prop_assert!(commit.is_char_boundary(issue.bytes().end()));
+            }
+        }
+    }
+
+    #[test]
+    fn displayed_commit_is_valid(
+        (kind, scope, breaking_change, desc) in header(),
+        body in body(),
+        footers in footers(),
+    ) {
+        let footers: Vec<CommitFooter> = footers
+            .iter()
+            .map(|(token, value)| CommitFooter::Simple(token, value))
+            .collect();
+        let commit = ConventionalCommit {
+            header: CommitHeader {
+                kind: &kind,
+                scope: scope.as_deref(),
+                breaking_change,
+                desc: &desc,
+            },
+            body: body.as_deref(),
+            footers: &footers,
+        }
+        .to_string();
+
+        let report = analyze(&commit, &Config::default()).unwrap();
+        prop_assert!(report.is_ok(), "{:?}: {:?}", commit, report.header);
+    }
+
+    #[test]
+    fn parsed_commit_is_displayed(
+        (kind, scope, breaking_change, desc) in header(),
+        body in body(),
+        footers in any_footers(),
+    ) {
+        let footers: Vec<CommitFooter> = footers
+            .iter()
+            .map(|(kind, token, separator, value)| match kind {
+                0 => CommitFooter::Simple(token, value),
+                1 => CommitFooter::BreakingChange(value),
+                _ => CommitFooter::Trailer(token, *separator, value),
+            })
+            .collect();
+        let commit = ConventionalCommit {
+            header: CommitHeader {
+                kind: &kind,
+                scope: scope.as_deref(),
+                breaking_change,
+                desc: &desc,
+            },
+            body: body.as_deref(),
+            footers: &footers,
+        };
+
+        let message = commit.to_string();
+        let parsed = ParsedCommit::parse(&message);
+        prop_assert_eq!(parsed.as_ref().map(ParsedCommit::as_commit), Ok(commit));
+    }
+
+    #[test]
+    fn fix_inserts_nothing_without_the_type(
+        commit in "[a-z]{1,8}[ :()!：（）\u{a0}]{0,3}[a-z ]{0,10}",
+    ) {
+        let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+        let report = analyze(&commit, &config).unwrap();
+        let typeless = report.header.iter().any(|issue| {
+            matches!(issue, header::Issue::Missing(Domain::Type, _) | header::Issue::Unknown(Domain::Type, _))
+        });
+        if typeless {
+            // the rest of the edits remove or replace with the shorter ASCII
+            let fixed = fix(&commit, &report);
+            prop_assert!(fixed.len() <= commit.len(), "{:?} -> {:?}", commit, fixed);
+        }
+    }
+
+    #[test]
+    fn fixed_commit_is_valid(commit in FIXABLE) {
+        let report = analyze(&commit, &Config::default()).unwrap();
+        // every case is expected to have the issues, all of them with the edits
+        prop_assume!(!report.header.is_empty());
+        prop_assume!(report.header.iter().all(|issue| !report.edits(issue).is_empty()));
+
+        let fixed = fix(&commit, &report);
+        let report = analyze(&fixed, &Config::default());
+        prop_assert!(
+            report.as_ref().is_ok_and(Report::is_ok),
+            "{:?} -> {:?}: {:?}",
+            commit,
+            fixed,
+            report
+        );
+    }
+}
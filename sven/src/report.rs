@@ -1,9 +1,94 @@
-use crate::footer_issue::footer;
+use crate::{
+    block::{Block, Status},
+    conventional_commit::CommitHeader,
+    domain::{Domain, Scope},
+    fix::Edit,
+    footer_issue::footer,
+    header_issue::header,
+    shape_issue::shape,
+};
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
+#[non_exhaustive]
 pub struct Report {
-    pub header: Vec<()>,
-    pub shape: (),
+    pub header: Vec<header::Issue>,
+    pub shape: Vec<shape::Issue>,
     pub footers: HashMap<usize, Vec<footer::Issue>>,
+
+    /// The solution picked for the header, starting with the root block
+    pub solution: Vec<Block>,
+
+    /// Scopes of the changed paths, see [`crate::analyze_paths`]
+    pub changed_scopes: Vec<String>,
+}
+
+impl Report {
+    /// Whether the commit fulfills the specification
+    pub fn is_ok(&self) -> bool {
+        self.header.is_empty()
+            && self.shape.is_empty()
+            && self.footers.values().all(|issues| issues.is_empty())
+    }
+
+    /// Header of the analysed commit, as long as it has no issues
+    ///
+    /// ```
+    /// use sven::{analyze, Config};
+    ///
+    /// let commit = "feat(app)!: me";
+    /// let header = analyze(commit, &Config::default()).unwrap().commit_header(commit).unwrap();
+    /// assert_eq!((header.kind, header.scope, header.breaking_change), ("feat", Some("app"), true));
+    /// ```
+    pub fn commit_header<'c>(&self, commit: &'c str) -> Option<CommitHeader<'c>> {
+        if !self.header.is_empty() {
+            return None;
+        }
+
+        let capture = |domain: Domain| {
+            self.solution
+                .iter()
+                .find(|block| block.domain == domain && block.status == Status::Settled)
+                .and_then(|block| block.capture(commit))
+        };
+
+        // only the reverted header is settled for the header git writes
+        // when reverting, see [`crate::Revert`]
+        let kind = match capture(Domain::Type) {
+            Some(kind) => kind,
+            None => capture(Domain::Desc).and(Some("revert"))?,
+        };
+
+        Some(CommitHeader {
+            kind,
+            scope: capture(Domain::Scope(Scope::Scope)),
+            desc: capture(Domain::Desc)?,
+            breaking_change: capture(Domain::Breaking).is_some(),
+        })
+    }
+
+    /// Edits fixing the issue of the header, see [`header::Issue::edits`],
+    /// except the insertions of the punctuation when the type itself is
+    /// missing or unknown: `chore: me` is better left as it is than made
+    /// `: chore: me`
+    ///
+    /// ```
+    /// use sven::{analyze, Config};
+    ///
+    /// let config = Config::parse(r#"types = ["feat", "fix"]"#).unwrap();
+    /// let report = analyze("chore: me", &config).unwrap();
+    /// assert!(report.header.iter().all(|issue| report.edits(issue).is_empty()));
+    /// ```
+    pub fn edits(&self, issue: &header::Issue) -> Vec<Edit> {
+        let typeless = self.header.iter().any(|issue| {
+            matches!(
+                issue,
+                header::Issue::Missing(Domain::Type, _) | header::Issue::Unknown(Domain::Type, _)
+            )
+        });
+        match issue {
+            header::Issue::Missing(..) if typeless => Vec::new(),
+            issue => issue.edits(),
+        }
+    }
 }
@@ -0,0 +1,30 @@
+pub mod shape {
+    use crate::message::{Arg, Locale, Message};
+    use std::fmt::Display;
+
+    /// Something that is wrong with the message as a whole
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub enum Issue {
+        /// The row ends with the line ending other than the one
+        /// the previous rows end with, e.g. `\r\n` after `\n`
+        MixedLineEndings(usize),
+    }
+
+    impl Issue {
+        /// Message of the issue, to be localised
+        pub fn message(&self) -> Message {
+            match self {
+                Issue::MixedLineEndings(row) => {
+                    Message::new("shape-mixed-line-endings").arg("row", Arg::Number(*row))
+                }
+            }
+        }
+    }
+
+    impl Display for Issue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.message().localize(Locale::En))
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{cleanup::Cleanup, config::Config};
+use std::fmt::Write;
+
+/// Commit message template to be used as git's `commit.template`, with
+/// comment lines describing what the commit is expected to look like
+///
+/// ```
+/// use sven::{template, Config};
+///
+/// let template = template(&Config::parse(r#"types = ["fix", "feat"]"#).unwrap());
+/// assert!(template.contains("# Types: feat, fix\n"));
+/// ```
+pub fn template(config: &Config) -> String {
+    let mut t = String::from("\n");
+    let comment = |t: &mut String, line: &str| self::comment(t, config.comment_char, line);
+
+    comment(&mut t, "<type>[(<scope>)][!]: <description>");
+    comment(&mut t, "");
+    comment(&mut t, "[optional body]");
+    comment(&mut t, "");
+    comment(&mut t, "[optional footer(s)]");
+    comment(&mut t, "");
+
+    let types = config.type_rule.sorted();
+    let types = types.map_or("any".to_string(), |types| types.join(", "));
+    comment(&mut t, &format!("Types: {}", types));
+
+    let scopes = config.scope_rule.sorted();
+    let scopes = scopes.map_or("any".to_string(), |scopes| scopes.join(", "));
+    comment(&mut t, &format!("Scopes: {}", scopes));
+
+    if !config.required_footers.is_empty() {
+        comment(
+            &mut t,
+            &format!("Required footers: {}", config.required_footers.join(", ")),
+        );
+    }
+
+    // the other modes leave the comments in the message
+    if config.cleanup == Cleanup::Strip {
+        comment(&mut t, "");
+        comment(
+            &mut t,
+            &format!(
+                "Lines starting with '{}' will be ignored.",
+                config.comment_char
+            ),
+        );
+    }
+
+    t
+}
+
+fn comment(t: &mut String, comment: char, line: &str) {
+    match line.is_empty() {
+        true => writeln!(t, "{}", comment),
+        false => writeln!(t, "{} {}", comment, line),
+    }
+    .expect("writing to string");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::analyze;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn default() {
+        let expected = r###"
+# <type>[(<scope>)][!]: <description>
+#
+# [optional body]
+#
+# [optional footer(s)]
+#
+# Types: any
+# Scopes: any
+#
+# Lines starting with '#' will be ignored.
+"###;
+        assert_eq!(template(&Config::default()), expected);
+    }
+
+    #[test]
+    fn full() {
+        let config = Config::parse(
+            r#"
+types = ["fix", "feat"]
+scopes = ["ui", "app"]
+footers = ["Refs", "Reviewed-by"]
+"#,
+        )
+        .unwrap();
+        let expected = r###"
+# <type>[(<scope>)][!]: <description>
+#
+# [optional body]
+#
+# [optional footer(s)]
+#
+# Types: feat, fix
+# Scopes: app, ui
+# Required footers: Refs, Reviewed-by
+#
+# Lines starting with '#' will be ignored.
+"###;
+        assert_eq!(template(&config), expected);
+    }
+
+    #[test]
+    fn comment_char_and_cleanup() {
+        let config = Config::parse(r#"comment_char = ";""#).unwrap();
+        assert!(template(&config).ends_with("\n;\n; Lines starting with ';' will be ignored.\n"));
+
+        for cleanup in ["whitespace", "scissors", "verbatim"] {
+            let source = format!("comment_char = \";\"\ncleanup = \"{}\"", cleanup);
+            let template = template(&Config::parse(&source).unwrap());
+            assert!(template.ends_with("; Scopes: any\n"), "{}", template);
+        }
+    }
+
+    #[test]
+    fn filled_in_template_is_valid() {
+        let commit = format!("fix: me{}", template(&Config::default()));
+        assert!(analyze(&commit, &Config::default()).unwrap().is_ok());
+    }
+}
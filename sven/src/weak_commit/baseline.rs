@@ -0,0 +1,150 @@
+//! The pest implementation the scanner replaced, kept to benchmark the
+//! scanner against and to check both produce the same rows and blocks
+
+use super::{Row, WeakCommit};
+use crate::{
+    block::{Block, Status, Val},
+    bytes::Bytes,
+    domain::Domain,
+};
+use anyhow::Result;
+use pest::Parser;
+use pest_derive::Parser;
+
+#[derive(Parser)]
+#[grammar = "./weak_commit/baseline.pest"] // relative to src
+struct CommitParser;
+
+/// Same as [`WeakCommit::parse`], the way it was done with pest
+pub fn parse(commit: &str) -> Result<WeakCommit> {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut row_n: usize = 1;
+
+    for rule in CommitParser::parse(Rule::Lines, commit)? {
+        if rule.as_rule() != Rule::Lines {
+            continue;
+        }
+
+        for rule in rule.into_inner() {
+            if let Rule::Row | Rule::RowEOL = rule.as_rule() {
+                let span = rule.as_span();
+                let value = rule.as_str();
+                if !value.is_empty() {
+                    rows.push(Row {
+                        row: row_n,
+                        blank: Row::probe_blank_line(value),
+                        bytes: Bytes::new(span.start(), span.end()),
+                    });
+                    row_n += 1;
+                }
+            }
+        }
+    }
+
+    let header = match rows.first() {
+        Some(row) => {
+            let mut header = parse_header(row.bytes.capture(commit).unwrap_or_default())?;
+            for block in header.iter_mut() {
+                block.bytes = block.bytes.map(|bytes| bytes.shift(row.bytes.start()));
+            }
+            header
+        }
+        None => parse_header("")?,
+    };
+
+    Ok(WeakCommit { header, rows })
+}
+
+fn parse_header(header: &str) -> Result<Vec<Block>> {
+    let mut word_bytes = 0;
+    let mut v = vec![Block::root()];
+    let mut prev = 0;
+
+    for rule in CommitParser::parse(Rule::Tokens, header)? {
+        if rule.as_rule() != Rule::Tokens {
+            continue;
+        }
+
+        for token in rule.into_inner() {
+            let span = token.as_span();
+            let val = match token.as_rule() {
+                Rule::TokenChar => {
+                    word_bytes += span.end() - span.start();
+                    continue;
+                }
+                Rule::TokenOpenBracket => Val::OpenBracket,
+                Rule::TokenCloseBracket => Val::CloseBracket,
+                Rule::TokenExclMark => Val::ExclMark,
+                Rule::TokenColon => Val::Colon,
+                Rule::TokenWhitespace => Val::Space,
+                Rule::TokenEOL => Val::EOL,
+                _ => continue,
+            };
+
+            prev = span.end();
+            if word_bytes > 0 {
+                v.push(block(
+                    Val::Seq,
+                    Bytes::new(span.start() - word_bytes, span.start()),
+                ));
+                word_bytes = 0;
+            }
+            v.push(block(val, Bytes::new(span.start(), span.end())));
+        }
+    }
+
+    if word_bytes > 0 {
+        v.push(block(Val::Seq, Bytes::new(prev, prev + word_bytes)));
+    }
+
+    Ok(v)
+}
+
+fn block(val: Val, bytes: Bytes) -> Block {
+    Block {
+        val,
+        domain: Domain::None,
+        bytes: Some(bytes),
+        status: Status::Unsigned,
+    }
+}
+
+#[cfg(test)]
+mod equivalence {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Inputs of the tests of the scanner and of the benchmark
+    const FIXTURES: &[&str] = &[
+        "",
+        "\n",
+        "one",
+        "one\n",
+        "one\n\n",
+        "just some text",
+        "fix(app)!: me",
+        "fix: да",
+        "fix（app）：\u{a0}да",
+        "fix: \t\u{3000}me",
+        "fix(app)!:  да\r\n",
+        "🚀✨:x",
+        "one\n\ntwo\n\nthree",
+        "one\r\n \r\ntwo\rthree",
+        "a\r\n\rb\n",
+        "раз\nдва",
+        "feat(parser)!: rebuild the row splitting\n\nRows and tokens.\n\n\
+         Кириллица, 漢字 and emoji 🚀.\n\nBREAKING CHANGE: gone\nRefs: #37\n",
+    ];
+
+    #[test]
+    fn same_rows_and_blocks() {
+        for commit in FIXTURES {
+            assert_eq!(
+                WeakCommit::parse(commit),
+                parse(commit).unwrap(),
+                "{:?}",
+                commit
+            );
+        }
+    }
+}
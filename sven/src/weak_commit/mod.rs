@@ -1,13 +1,11 @@
-use self::{
-    parse_header::parse_header,
-    parser::{CRule, CommitParser},
-};
-use crate::{block::Block, bytes::Bytes};
-use anyhow::Result;
-use pest::Parser;
+use self::{parse_header::parse_header, scan::Rows};
+use crate::block::Block;
 
 pub mod parse_header;
-mod parser;
+mod scan;
+
+#[cfg(feature = "baseline")]
+pub mod baseline;
 
 mod row;
 pub use row::Row;
@@ -19,57 +17,44 @@ pub struct WeakCommit {
 }
 
 impl WeakCommit {
-    pub fn parse(commit: &str) -> Result<Self> {
-        let mut rows: Vec<Row> = Vec::new();
-        let mut row_n: usize = 1;
-
-        let rules = CommitParser::parse(CRule::Lines, commit)?;
-
-        for rule in rules {
-            match rule.as_rule() {
-                CRule::Lines => {
-                    for rule in rule.into_inner() {
-                        match rule.as_rule() {
-                            CRule::Row | CRule::RowEOL => {
-                                let span = rule.as_span();
-                                let value = rule.as_str();
-                                if !value.is_empty() {
-                                    rows.push(Row {
-                                        row: row_n,
-                                        blank: Row::probe_blank_line(value),
-                                        bytes: Bytes::new(span.start(), span.end()),
-                                    });
-                                    row_n += 1;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
+    /// Split the commit into rows, any of `\n`, `\r\n` and `\r` ends the row
+    /// and stays within its bytes, then tokenize the header
+    pub fn parse(commit: &str) -> Self {
+        let rows: Vec<Row> = Rows::new(commit).collect();
 
         let header = match rows.first() {
-            Some(row) => {
-                let header_str = row.bytes.capture(&commit);
-                parse_header(header_str.expect("could not extract header string"))?
-            }
-            None => parse_header("")?,
+            Some(row) => header_at(commit, row, 0),
+            None => parse_header(""),
         };
 
-        Ok(Self { rows, header })
+        Self { rows, header }
     }
 }
 
+/// Blocks of the header found in the row, starting from the given byte of
+/// the row, with the bytes pointing to the bytes of the commit
+pub(crate) fn header_at(commit: &str, row: &Row, skip: usize) -> Vec<Block> {
+    let start = row.bytes.start() + skip;
+    let header = commit.get(start..row.bytes.end()).unwrap_or_default();
+
+    parse_header(header)
+        .into_iter()
+        .map(|block| Block {
+            bytes: block.bytes.map(|bytes| bytes.shift(start)),
+            ..block
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod producing {
     use super::*;
+    use crate::bytes::Bytes;
     use pretty_assertions::assert_eq;
 
     #[test]
     fn singleline() {
-        let actual = WeakCommit::parse("fix(app)!: me").unwrap();
+        let actual = WeakCommit::parse("fix(app)!: me");
         let expected = vec![Row {
             row: 1,
             blank: 0,
@@ -80,7 +65,7 @@ mod producing {
 
     #[test]
     fn multiline() {
-        let actual = WeakCommit::parse("one\n\ntwo\n\nthree").unwrap();
+        let actual = WeakCommit::parse("one\n\ntwo\n\nthree");
         let expected = vec![
             Row {
                 row: 1,
@@ -111,9 +96,28 @@ mod producing {
         assert_eq!(actual.rows, expected);
     }
 
+    #[test]
+    fn line_endings() {
+        let actual = WeakCommit::parse("one\r\n \r\ntwo\rthree");
+        let blank: Vec<(usize, u8, Bytes)> = actual
+            .rows
+            .iter()
+            .map(|row| (row.row, row.blank, row.bytes))
+            .collect();
+        assert_eq!(
+            blank,
+            vec![
+                (1, 0, Bytes::new(0, 5)),
+                (2, 1, Bytes::new(5, 8)),
+                (3, 0, Bytes::new(8, 12)),
+                (4, 0, Bytes::new(12, 17)),
+            ]
+        );
+    }
+
     #[test]
     fn multiline_utf8() {
-        let actual = WeakCommit::parse("раз\nдва").unwrap();
+        let actual = WeakCommit::parse("раз\nдва");
         let expected = vec![
             Row {
                 row: 1,
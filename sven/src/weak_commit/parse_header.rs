@@ -1,132 +1,24 @@
-use super::{CRule, CommitParser};
-use crate::{
-    block::{Block, Status, Val},
-    bytes::Bytes,
-    domain::Domain,
-};
-use anyhow::Result;
-use pest::Parser;
+use super::scan::Tokens;
+use crate::block::Block;
 
-pub fn parse_header(header: &str) -> Result<Vec<Block>> {
-    let mut word_bytes = 0;
-    let mut v = vec![Block {
-        val: Val::Root,
-        domain: Domain::Root,
-        bytes: None,
-        status: Status::Settled,
-    }];
-
-    let rules = CommitParser::parse(CRule::Tokens, header)?;
-    let mut prev = 0;
-
-    for rule in rules {
-        match rule.as_rule() {
-            CRule::Tokens => {
-                for token in rule.into_inner() {
-                    let span = token.as_span();
-                    let rule = token.as_rule();
-
-                    match rule {
-                        CRule::TokenChar => {
-                            let bytes = span.end() - span.start();
-                            word_bytes += bytes;
-                            continue;
-                        }
-                        _ => {
-                            prev = span.end();
-                            if word_bytes > 0 {
-                                v.push(Block {
-                                    val: Val::Seq,
-                                    bytes: Some(Bytes::new(
-                                        span.start() - word_bytes,
-                                        span.end() - 1,
-                                    )),
-                                    domain: Domain::None,
-                                    status: Status::Unsigned,
-                                });
-                                word_bytes = 0;
-                            }
-                        }
-                    }
-
-                    match rule {
-                        CRule::TokenOpenBracket => {
-                            v.push(Block {
-                                val: Val::OpenBracket,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        CRule::TokenCloseBracket => {
-                            v.push(Block {
-                                val: Val::CloseBracket,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        CRule::TokenExclMark => {
-                            v.push(Block {
-                                val: Val::ExclMark,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        CRule::TokenColon => {
-                            v.push(Block {
-                                val: Val::Colon,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        CRule::TokenWhitespace => {
-                            v.push(Block {
-                                val: Val::Space,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        CRule::TokenEOL => {
-                            v.push(Block {
-                                val: Val::EOL,
-                                domain: Domain::None,
-                                bytes: Some(span.into()),
-                                status: Status::Unsigned,
-                            });
-                        }
-                        _ => {}
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-
-    if word_bytes > 0 {
-        v.push(Block {
-            val: Val::Seq,
-            domain: Domain::None,
-            bytes: Some(Bytes::new(prev, prev + word_bytes)),
-            status: Status::Unsigned,
-        });
-    }
-
-    Ok(v)
+pub fn parse_header(header: &str) -> Vec<Block> {
+    Tokens::new(header).collect()
 }
 
 #[cfg(test)]
 mod rows {
     use super::*;
+    use crate::{
+        block::{Status, Val},
+        bytes::Bytes,
+        domain::Domain,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
     fn empty() {
         let source = String::from("");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![Block::root()];
         assert_eq!(actual, expected);
     }
@@ -134,7 +26,7 @@ mod rows {
     #[test]
     fn eol() {
         let source = String::from("\n");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -150,7 +42,7 @@ mod rows {
     #[test]
     fn one_word() {
         let source = String::from("one");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -166,7 +58,7 @@ mod rows {
     #[test]
     fn one_word_and_eol() {
         let source = String::from("one\n");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -188,7 +80,7 @@ mod rows {
     #[test]
     fn text() {
         let source = String::from("just some text");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -228,7 +120,7 @@ mod rows {
     #[test]
     fn commit() {
         let source = String::from("fix(app)!: me");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -286,7 +178,7 @@ mod rows {
     #[test]
     fn utf8() {
         let source = String::from("fix: да");
-        let actual = parse_header(&source).unwrap();
+        let actual = parse_header(&source);
         let expected = vec![
             Block::root(),
             Block {
@@ -316,4 +208,90 @@ mod rows {
         ];
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn confusables() {
+        let source = String::from("fix（app）：\u{a0}да");
+        let actual = parse_header(&source);
+        let expected = vec![
+            Block::root(),
+            Block {
+                val: Val::Seq,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(0, 3)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::OpenBracket,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(3, 6)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Seq,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(6, 9)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::CloseBracket,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(9, 12)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Colon,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(12, 15)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Space,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(15, 17)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Seq,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(17, 21)),
+                status: Status::Unsigned,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn whitespace_run() {
+        let source = String::from("fix: \t\u{3000}me");
+        let actual = parse_header(&source);
+        let expected = vec![
+            Block::root(),
+            Block {
+                val: Val::Seq,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(0, 3)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Colon,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(3, 4)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Space,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(4, 9)),
+                status: Status::Unsigned,
+            },
+            Block {
+                val: Val::Seq,
+                domain: Domain::None,
+                bytes: Some(Bytes::new(9, 11)),
+                status: Status::Unsigned,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,5 +0,0 @@
-#[derive(Parser)]
-#[grammar = "./weak_commit/grammar.pest"] // relative to src
-pub struct CommitParser;
-
-pub type CRule = Rule;
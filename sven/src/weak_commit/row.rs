@@ -1,6 +1,4 @@
-use super::parser::{CRule, CommitParser};
 use crate::bytes::Bytes;
-use pest::Parser;
 
 /// Metadata about each row.
 #[derive(Debug, PartialEq)]
@@ -10,8 +8,8 @@ pub struct Row {
     /// The row starting 1.
     pub row: usize,
 
-    /// 1 for the new line,
-    /// 0 for any other character
+    /// 1 for the row made of whitespace and the line ending only,
+    /// 0 for any other row
     pub blank: u8,
 }
 
@@ -20,25 +18,29 @@ impl Row {
     /// `lines[row.row_index()]` in a safe way.
     #[inline]
     pub fn row_index(&self) -> usize {
-        self.row.checked_sub(1).unwrap_or(0)
+        self.row.saturating_sub(1)
     }
 
     pub fn probe_blank_line(value: &str) -> u8 {
-        match CommitParser::parse(CRule::ProbeBlankLine, value) {
-            Ok(rules) => {
-                for rule in rules {
-                    match rule.as_rule() {
-                        CRule::ProbeEOL => return 1,
-                        CRule::ProbeChar => return 0,
-                        _ => unreachable!(),
-                    };
-                }
-            }
-            Err(e) => {
-                panic!("{}", e);
-            }
-        };
-
-        unreachable!()
+        value.chars().all(char::is_whitespace).into()
+    }
+}
+
+#[cfg(test)]
+mod probe_blank_line {
+    use super::*;
+
+    #[test]
+    fn blank() {
+        for value in ["\n", "\r\n", "\r", " \t\n", "\u{a0}\r\n"] {
+            assert_eq!(Row::probe_blank_line(value), 1, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn not_blank() {
+        for value in ["a\n", " a\r\n", "a"] {
+            assert_eq!(Row::probe_blank_line(value), 0, "{:?}", value);
+        }
     }
 }
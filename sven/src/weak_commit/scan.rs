@@ -0,0 +1,222 @@
+use super::Row;
+use crate::{
+    block::{Block, Status, Val},
+    bytes::Bytes,
+    domain::Domain,
+};
+
+/// Rows of the commit, scanned in a single pass as they are asked for. Every
+/// row keeps its line ending, one of `\n`, `\r\n` or `\r`, within its bytes.
+pub struct Rows<'c> {
+    commit: &'c str,
+    start: usize,
+    row: usize,
+}
+
+impl<'c> Rows<'c> {
+    pub fn new(commit: &'c str) -> Self {
+        Self {
+            commit,
+            start: 0,
+            row: 1,
+        }
+    }
+}
+
+impl Iterator for Rows<'_> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.commit[self.start..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let len = match rest.find(['\n', '\r']) {
+            Some(at) => at + eol(&rest[at..]),
+            None => rest.len(),
+        };
+
+        let value = &rest[..len];
+        let row = Row {
+            row: self.row,
+            blank: Row::probe_blank_line(value),
+            bytes: Bytes::new(self.start, self.start + len),
+        };
+
+        self.start += len;
+        self.row += 1;
+        Some(row)
+    }
+}
+
+/// Blocks of the header, starting with the root one, scanned in a single
+/// pass as they are asked for. Runs of chars other than the punctuation make
+/// a single `Val::Seq` block, as well as runs of whitespace make a single
+/// `Val::Space` block.
+pub struct Tokens<'h> {
+    header: &'h str,
+    start: usize,
+    root: bool,
+}
+
+impl<'h> Tokens<'h> {
+    pub fn new(header: &'h str) -> Self {
+        Self {
+            header,
+            start: 0,
+            root: true,
+        }
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.root {
+            self.root = false;
+            return Some(Block::root());
+        }
+
+        let rest = &self.header[self.start..];
+        let (val, len) = match rest.chars().next()? {
+            '\n' | '\r' => (Val::EOL, eol(rest)),
+            c if is_space(c) => (Val::Space, run(rest, is_space)),
+            c => match punctuation(c) {
+                Some(val) => (val, c.len_utf8()),
+                None => (Val::Seq, run(rest, |c| !is_structural(c))),
+            },
+        };
+
+        let bytes = Bytes::new(self.start, self.start + len);
+        self.start += len;
+
+        Some(Block {
+            val,
+            domain: Domain::None,
+            bytes: Some(bytes),
+            status: Status::Unsigned,
+        })
+    }
+}
+
+/// Length of the line ending the text starts with
+#[inline]
+fn eol(text: &str) -> usize {
+    match text.as_bytes() {
+        [b'\r', b'\n', ..] => 2,
+        [b'\n' | b'\r', ..] => 1,
+        _ => 0,
+    }
+}
+
+/// Length of the run of chars the text starts with
+#[inline]
+fn run(text: &str, f: impl Fn(char) -> bool) -> usize {
+    text.find(|c: char| !f(c)).unwrap_or(text.len())
+}
+
+/// Punctuation of the header, full-width punctuation typed with CJK input
+/// methods is confusable with the ASCII one and is scanned the same way
+#[inline]
+fn punctuation(c: char) -> Option<Val> {
+    match c {
+        '(' | '（' => Some(Val::OpenBracket),
+        ')' | '）' => Some(Val::CloseBracket),
+        '!' | '！' => Some(Val::ExclMark),
+        ':' | '：' => Some(Val::Colon),
+        _ => None,
+    }
+}
+
+/// Tab and the chars of the Unicode space separator category, such as
+/// the no-break space
+#[inline]
+fn is_space(c: char) -> bool {
+    matches!(
+        c,
+        ' ' | '\t' | '\u{00A0}' | '\u{1680}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    ) || ('\u{2000}'..='\u{200A}').contains(&c)
+}
+
+#[inline]
+fn is_structural(c: char) -> bool {
+    c == '\n' || c == '\r' || is_space(c) || punctuation(c).is_some()
+}
+
+#[cfg(test)]
+mod rows {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rows(commit: &str) -> Vec<(usize, u8, Bytes)> {
+        Rows::new(commit)
+            .map(|row| (row.row, row.blank, row.bytes))
+            .collect()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(rows(""), vec![]);
+    }
+
+    #[test]
+    fn trailing_newline() {
+        assert_eq!(
+            rows("one\n\n"),
+            vec![(1, 0, Bytes::new(0, 4)), (2, 1, Bytes::new(4, 5))]
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(
+            rows("a\r\n\rb\n"),
+            vec![
+                (1, 0, Bytes::new(0, 3)),
+                (2, 1, Bytes::new(3, 4)),
+                (3, 0, Bytes::new(4, 6)),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tokens {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn tokens(header: &str) -> Vec<(Val, &str)> {
+        Tokens::new(header)
+            .skip(1)
+            .map(|block| (block.val, block.capture(header).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn commit() {
+        assert_eq!(
+            tokens("fix(app)!:  да\r\n"),
+            vec![
+                (Val::Seq, "fix"),
+                (Val::OpenBracket, "("),
+                (Val::Seq, "app"),
+                (Val::CloseBracket, ")"),
+                (Val::ExclMark, "!"),
+                (Val::Colon, ":"),
+                (Val::Space, "  "),
+                (Val::Seq, "да"),
+                (Val::EOL, "\r\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn seq_of_emoji() {
+        assert_eq!(
+            tokens("🚀✨:x"),
+            vec![(Val::Seq, "🚀✨"), (Val::Colon, ":"), (Val::Seq, "x")]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::Repo;
+use pretty_assertions::assert_eq;
+
+fn changelog(repo: &Repo) -> String {
+    let output = repo.sven(&["changelog"], "");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn tagged_head() {
+    let repo = Repo::new(false);
+    repo.commit("feat: one");
+    repo.git(&["tag", "v1.0.0"]);
+    repo.commit("feat: two");
+    repo.commit("fix: three");
+
+    // the release is the same right before HEAD is tagged and right after
+    let untagged = changelog(&repo);
+    repo.git(&["tag", "v1.1.0"]);
+    let tagged = changelog(&repo);
+    assert_eq!(tagged, untagged);
+
+    assert!(tagged.starts_with("## v1.1.0"), "{}", tagged);
+    assert!(
+        tagged.contains("* two") && tagged.contains("* three"),
+        "{}",
+        tagged
+    );
+    assert!(!tagged.contains("* one"), "{}", tagged);
+}
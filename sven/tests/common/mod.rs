@@ -0,0 +1,91 @@
+//! Temporary git repositories the CLI is run in
+
+// every test uses its own part of the helpers
+#![allow(dead_code)]
+
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+use tempfile::TempDir;
+
+pub struct Repo {
+    dir: TempDir,
+}
+
+impl Repo {
+    /// Empty repository, bare or with the work tree
+    pub fn new(bare: bool) -> Self {
+        let repo = Self {
+            dir: TempDir::new().expect("temporary directory"),
+        };
+        let mut args = vec!["init", "-q", "-b", "main"];
+        if bare {
+            args.push("--bare");
+        }
+        repo.git(&args);
+        repo.git(&["config", "user.name", "sven"]);
+        repo.git(&["config", "user.email", "sven@example.com"]);
+        repo
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Run git in the repository, returning its stdout
+    pub fn git(&self, args: &[&str]) -> String {
+        let output = self.git_output(args);
+        assert!(
+            output.status.success(),
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).expect("UTF-8 output")
+    }
+
+    /// Run git in the repository, whether it succeeds or not
+    pub fn git_output(&self, args: &[&str]) -> Output {
+        self.command("git").args(args).output().expect("git runs")
+    }
+
+    /// Commit nothing with the message, returning the SHA of the commit
+    pub fn commit(&self, message: &str) -> String {
+        self.git(&["commit", "-q", "--allow-empty", "-m", message]);
+        self.git(&["rev-parse", "HEAD"]).trim().to_string()
+    }
+
+    /// Run sven in the repository with the input
+    pub fn sven(&self, args: &[&str], input: &str) -> Output {
+        let mut sven = self
+            .command(env!("CARGO_BIN_EXE_sven"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("sven runs");
+        sven.stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(input.as_bytes())
+            .expect("input is written");
+        sven.wait_with_output().expect("sven exits")
+    }
+
+    /// Command run in the repository, away from the config of the user and
+    /// the language of the environment
+    fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(program);
+        command
+            .current_dir(self.path())
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("LC_ALL", "C")
+            .env_remove("LC_MESSAGES")
+            .env_remove("LANG");
+        command
+    }
+}
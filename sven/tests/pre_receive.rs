@@ -0,0 +1,78 @@
+mod common;
+
+use common::Repo;
+use std::{fs, os::unix::fs::PermissionsExt, process::Output};
+
+/// Bare repository on the server, running sven as its `pre-receive` hook,
+/// along with the clone pushing to it
+fn server_and_clone() -> (Repo, Repo) {
+    let server = Repo::new(true);
+    let hook = server.path().join("hooks").join("pre-receive");
+    let script = format!(
+        "#!/bin/sh\nexec '{}' pre-receive\n",
+        env!("CARGO_BIN_EXE_sven")
+    );
+    fs::write(&hook, script).unwrap();
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let clone = Repo::new(false);
+    let url = server.path().to_str().unwrap();
+    clone.git(&["remote", "add", "origin", url]);
+    (server, clone)
+}
+
+fn push(clone: &Repo, refs: &[&str]) -> Output {
+    let mut args = vec!["push", "-q", "origin"];
+    args.extend(refs);
+    clone.git_output(&args)
+}
+
+#[test]
+fn create_update_delete() {
+    let (server, clone) = server_and_clone();
+
+    clone.commit("feat: one");
+    let output = push(&clone, &["main"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let bad = clone.commit("two");
+    let output = push(&clone, &["main"]);
+    assert!(!output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("{} refs/heads/main two", &bad[..7])),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("push rejected, 1 commit(s)"), "{}", stderr);
+
+    clone.git(&["commit", "-q", "--amend", "--allow-empty", "-m", "fix: two"]);
+    let good = clone.git(&["rev-parse", "HEAD"]).trim().to_string();
+    let output = push(&clone, &["main"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(server.git(&["rev-parse", "main"]).trim(), good);
+
+    clone.git(&["branch", "fix/two"]);
+    let output = push(&clone, &["fix/two"]);
+    assert!(output.status.success(), "{:?}", output);
+    let output = push(&clone, &[":fix/two"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(server.git(&["branch", "--list", "fix/two"]), "");
+}
+
+#[test]
+fn commit_of_several_refs() {
+    let (_server, clone) = server_and_clone();
+    clone.commit("feat: one");
+    clone.commit("two");
+    clone.git(&["branch", "other"]);
+
+    let output = push(&clone, &["main", "other"]);
+    assert!(!output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let analysed = stderr
+        .lines()
+        .filter(|line| line.trim_end().ends_with(" two"));
+    assert_eq!(analysed.count(), 1, "{}", stderr);
+    assert!(stderr.contains("push rejected, 1 commit(s)"), "{}", stderr);
+}
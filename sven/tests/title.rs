@@ -0,0 +1,48 @@
+mod common;
+
+use common::Repo;
+use pretty_assertions::assert_eq;
+use std::process::Output;
+
+fn title(args: &[&str]) -> Output {
+    let mut all = vec!["title"];
+    all.extend(args);
+    Repo::new(false).sven(&all, "")
+}
+
+#[test]
+fn not_a_single_line() {
+    let output = title(&["--format", "json", "fix: me\n\nBody"]);
+    assert!(!output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[{\"start\":7,\"end\":8,\"message\":\"line ending at byte 7\"}]\n"
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+
+    let output = title(&["--format=json", " "]);
+    assert!(!output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[{\"message\":\"empty commit message\"}]\n"
+    );
+
+    let output = title(&["fix: me\n\nBody"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "7..8: line ending at byte 7\n"
+    );
+}
+
+#[test]
+fn after_the_separator() {
+    let output = title(&["--", "fix: me"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    // taken for the title, not for the option
+    let output = title(&["--format", "json", "--", "--format"]);
+    assert!(!output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("[{\"start\":"), "{}", stdout);
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}